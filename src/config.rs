@@ -1,37 +1,140 @@
-use std::fmt::Display;
+use std::{fmt::Display, path::PathBuf};
 
 use crate::mapping::Mapping;
 
+/// Most times per second a source can be polled.
+pub const MAX_SOURCE_POLL_RATE: u32 = 2000;
+
 #[derive(Debug, Clone)]
 pub struct Config {
     /// How many updates per second.
     pub update_frequency: u32,
+    /// Run as many physics steps as the real time passed calls for, instead of
+    /// exactly one per update.
+    pub fixed_timestep: bool,
+    /// Minimum times per second that device events (i.e. force-feedback) are serviced.
+    pub event_poll_frequency: u32,
     /// Angular range (in degrees) that the steering wheel has lock-to-lock.
     pub range: f32,
+    /// What happens when the free wheel hits the end of its range.
+    pub lock_behavior: LockBehavior,
+    /// Whether the horn can be sounded at all.
+    pub horn_enabled: bool,
     /// Maximum threshold in which bringing the pen down triggers the horn.
     pub horn_radius: f32,
+    /// Distance past `horn_radius` the pen must move before the horn is released.
+    pub horn_hysteresis: f32,
+    /// Time (in seconds) after letting go of the wheel before the horn can be pressed again.
+    pub horn_cooldown: f32,
+    /// Time (in milliseconds) the pen must rest in the centre before the horn sounds.
+    pub horn_dwell_ms: u32,
     /// Minimum units of pressure required for the pen to be considered touching.
     pub pressure_threshold: u32,
+    /// Pressure above which the clutch binding is held, while still steering. 0 disables it.
+    pub clutch_pressure: u32,
+    /// Bit of the pen buttons which recentres the wheel when pressed, if any.
+    pub recenter_button: Option<u8>,
+    /// Bit of the pen buttons which must be held for the pen to steer, if any.
+    pub engage_button: Option<u8>,
+    /// What touching down with the eraser end of the pen does.
+    pub eraser_action: EraserAction,
+    /// Whether a second finger or contact sounds the horn while the first one steers.
+    pub second_contact_horn: bool,
+    /// How pen movement is interpreted as steering.
+    pub steering_model: SteeringModel,
+    /// Keep the wheel where it is when the pen lands, steering relative to that
+    /// point instead of snapping to it. Only matters for planar steering.
+    pub relative_engage: bool,
     /// Smallest radius in which angular velocity will be computed.
     pub base_radius: f32,
+    /// How far the wheel turns for a given pen movement; 1.0 follows the pen exactly.
+    pub sensitivity: f32,
 
     /// Rotational inertia (in kg*m^2) of the simulated steering wheel.
     pub inertia: f32,
     /// Rotational friction coefficient.
     pub friction: f32,
+    /// Rotational friction coefficient proportional to the square of velocity.
+    pub friction_quadratic: f32,
+    /// Extra friction (in Nm·s/rad) just after letting go of the wheel, fading out
+    /// over half a second. 0 lets the wheel go without any.
+    pub release_damping: f32,
     /// Centring factor.
     pub spring: f32,
+    /// Exponent of the spring's curve. 1 is linear, while higher values centre
+    /// gently near straight ahead and more strongly towards lock.
+    pub spring_exp: f32,
+    /// Largest torque the spring may apply (in Nm), however far the wheel is
+    /// turned. 0 is unlimited.
+    pub max_spring_torque: f32,
+    /// Extra centring (in Nm/rad) only while the wheel turns away from centre, so
+    /// that it returns more easily than it leaves. 0 keeps both directions alike.
+    pub caster: f32,
+    /// Evenly spaced notches across the range that the free wheel settles into. 0 disables.
+    pub detents: u32,
+    /// How strongly the wheel is pulled towards the nearest detent (in Nm/rad).
+    pub detent_strength: f32,
+    /// Angle (in degrees) either side of straight ahead in which the centre assist acts.
+    pub center_assist_band: f32,
+    /// How strongly a slow, free wheel is pulled straight within the centre band (in Nm/rad).
+    pub center_assist_strength: f32,
     /// Maximum feedback torque that can be applied (in Nm).
     pub max_torque: f32,
+    /// Maximum rate of change of the feedback torque (in Nm/s). 0 is unlimited.
+    pub feedback_slew: f32,
+    /// Whether the game's force feedback pushes the wrong way and should be flipped.
+    pub feedback_invert: bool,
+    /// Safety cap on how quickly the feedback torque may grow (in Nm/s). 0 disables it.
+    pub feedback_rise_limit: f32,
+    /// Overall trade-off from 0.0 (most responsive) to 1.0 (smoothest), which the
+    /// individual smoothing settings are derived from. See `apply_smoothness`.
+    /// `None` until chosen, leaving the individual settings as they are.
+    pub smoothness: Option<f32>,
+    /// Time constant (in seconds) for easing towards the angle slider while dragging it. 0 snaps.
+    pub slider_easing: f32,
+    /// Whether the wheel returns to and holds straight ahead while there is no pen input.
+    pub center_when_idle: bool,
 
     /// Information to map source input to normalised coordinates.
     pub mapping: Mapping,
 
     /// Socket address to listen for data from, if using a `Net` source.
     pub net_sock_addr: String,
+    /// Whether a `Net` source repeats the last sample (marked stale) when no new one arrives.
+    pub net_hold_last: bool,
+    /// Whether a `Net` source only listens to the first sender until it goes quiet.
+    pub net_lock_sender: bool,
+    /// Times per second a `Net` source is polled. 0 follows the update frequency.
+    pub net_poll_rate: u32,
+    /// Most packets a `Net` source reads per poll. 0 is unlimited.
+    pub net_max_drain: u32,
 
+    /// Name of the key which pauses the simulation and neutralises the output.
+    pub panic_key: String,
+    /// Hold the output neutral and stop the wheel while the GUI window isn't focused.
+    pub pause_when_unfocused: bool,
+    /// Time (in seconds) over which a newly created device eases in to the current angle.
+    pub output_ramp: f32,
+    /// Time (in milliseconds) to wait at startup before creating the device.
+    pub startup_delay_ms: u32,
+    /// Shift of the output's neutral, as a fraction of the axis from the centre to
+    /// either end. The output is still kept within the axis.
+    pub output_center_offset: f32,
     /// Absolute axis resolution for the virtual device to present.
     pub device_resolution: u32,
+    /// Buttons pressed on a `UInput` device by each input.
+    pub bindings: Bindings,
+    /// Whether pen tilt is sent as lean on an extra axis of a `UInput` device.
+    pub lean_output: bool,
+    /// Axis of the virtual device which lean is sent on.
+    pub lean_axis: AuxAxis,
+    /// Whether a `UInput` device sends the horn at full scale on an extra axis
+    /// instead of pressing its button binding.
+    pub horn_axis_output: bool,
+    /// Axis of the virtual device which the horn is sent on.
+    pub horn_axis: AuxAxis,
+    /// Whether a low resolution uinput axis eases towards new values over a few updates.
+    pub device_smoothing: bool,
     /// Virtual device name.
     pub device_name: String,
     /// Virtual device vendor.
@@ -41,13 +144,139 @@ pub struct Config {
     /// Virtual device version.
     pub device_version: u16,
 
+    /// Multiplier applied to the ViGEm left stick output before clamping.
+    pub vigem_scale: f32,
+    /// Whether to invert the ViGEm left stick direction.
+    pub vigem_invert: bool,
+    /// Most updates per second sent to ViGEm. 0 is unlimited.
+    pub vigem_max_rate: u32,
+
+    /// Key tapped while the wheel is turned left, if using a `Keys` device.
+    pub keys_left: String,
+    /// Key tapped while the wheel is turned right, if using a `Keys` device.
+    pub keys_right: String,
+    /// Normalised wheel deflection below which no keys are pressed.
+    pub keys_deadzone: f32,
+    /// Normalised wheel deflection from which keys are held down continuously.
+    pub keys_saturation: f32,
+
+    /// Pixels the mouse moves per degree the wheel turns, if using a `Mouse` device.
+    pub mouse_pixels_per_degree: f32,
+    /// Degrees either side of centre which don't move the mouse.
+    pub mouse_deadzone: f32,
+
+    /// Image file drawn as the outer part of the wheel instead of the bundled one.
+    pub wheel_base_image: Option<String>,
+    /// Image file drawn as the centre (horn) of the wheel instead of the bundled one.
+    pub wheel_inner_image: Option<String>,
+
     /// Name of preferred tablet, if any.
     pub preferred_tablet: Option<String>,
+    /// Device node an `Evdev` source opens directly, e.g. under `/dev/input/by-id`,
+    /// instead of looking for a tablet by name.
+    pub evdev_path: Option<PathBuf>,
+    /// Times per second an `Evdev` source is polled. 0 follows the update frequency.
+    pub evdev_poll_rate: u32,
+    /// Absolute axes of an `Evdev` source read as the pen's X and Y, by name.
+    /// `None` uses the usual position axes.
+    pub evdev_x_axis: Option<String>,
+    pub evdev_y_axis: Option<String>,
 
     pub source: Source,
+    /// Second source read alongside `source`, e.g. a phone sending buttons.
+    pub merge_source: Source,
+    /// Which parts of the pen are taken from `merge_source`.
+    pub merge_policy: MergePolicy,
     pub device: Device,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SteeringModel {
+    /// Circling the pen around the centre turns the wheel.
+    Radial,
+    /// The pen's horizontal position sets the wheel angle directly.
+    Planar,
+}
+
+/// Names of the virtual device buttons pressed by each input. Empty names are unbound.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bindings {
+    /// Pressing the centre of the wheel.
+    pub horn: String,
+    /// Each of the first three pen buttons.
+    pub pen_buttons: [String; 3],
+    /// Touching down with the eraser.
+    pub eraser: String,
+    /// Pressing harder than the clutch pressure.
+    pub clutch: String,
+}
+
+impl Default for Bindings {
+    fn default() -> Self {
+        Self {
+            horn: "ButtonThumbr".into(),
+            pen_buttons: Default::default(),
+            eraser: String::new(),
+            clutch: "ButtonWest".into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LockBehavior {
+    /// Stop dead at the lock.
+    HardStop,
+    /// Rebound off the lock, losing some speed.
+    Bounce,
+}
+
+/// Bundles of settings which are a good starting point for some kinds of game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GamePreset {
+    /// Most sims which support a Logitech wheel.
+    RacingSim,
+    /// Euro Truck Simulator 2 and American Truck Simulator.
+    TruckSim,
+    /// Arcade racers played with a self-centring stick.
+    Arcade,
+}
+
+impl GamePreset {
+    pub const ALL: [GamePreset; 3] = [
+        GamePreset::RacingSim,
+        GamePreset::TruckSim,
+        GamePreset::Arcade,
+    ];
+}
+
+/// Extra axis of the virtual device, besides the steering axis.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuxAxis {
+    Y,
+    Z,
+    Rx,
+    Ry,
+    Rz,
+}
+
+impl AuxAxis {
+    pub const ALL: [AuxAxis; 5] = [
+        AuxAxis::Y,
+        AuxAxis::Z,
+        AuxAxis::Rx,
+        AuxAxis::Ry,
+        AuxAxis::Rz,
+    ];
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EraserAction {
+    /// Same as the pen tip.
+    Steer,
+    /// Sound the horn while touching.
+    Horn,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Source {
     None,
@@ -58,12 +287,23 @@ pub enum Source {
     Evdev,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MergePolicy {
+    /// Buttons held on either source count.
+    Buttons,
+    /// As with `Buttons`, and the pressure comes from the second source.
+    PressureAndButtons,
+}
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Device {
     None,
     #[cfg(target_os = "linux")]
     UInput,
+    #[cfg(target_os = "linux")]
+    Keys,
+    #[cfg(target_os = "linux")]
+    Mouse,
     #[cfg(target_os = "windows")]
     VigemBus,
 }
@@ -72,30 +312,87 @@ impl Default for Config {
     fn default() -> Self {
         Self {
             update_frequency: 125,
+            fixed_timestep: false,
+            event_poll_frequency: 250,
             range: 1800.0,
+            lock_behavior: LockBehavior::HardStop,
+            horn_enabled: true,
             horn_radius: 0.3,
+            horn_hysteresis: 0.1,
+            horn_cooldown: 0.15,
+            horn_dwell_ms: 0,
             pressure_threshold: 10,
+            clutch_pressure: 0,
+            recenter_button: None,
+            engage_button: None,
+            eraser_action: EraserAction::Steer,
+            second_contact_horn: false,
+            steering_model: SteeringModel::Radial,
+            relative_engage: false,
             base_radius: 0.6,
+            sensitivity: 1.0,
             inertia: 1.0,
             friction: 25.0,
+            friction_quadratic: 0.0,
+            release_damping: 0.0,
             spring: 0.0,
+            spring_exp: 1.0,
+            max_spring_torque: 0.0,
+            caster: 0.0,
+            detents: 0,
+            detent_strength: 5.0,
+            center_assist_band: 5.0,
+            center_assist_strength: 0.0,
             max_torque: 300.0,
+            feedback_slew: 0.0,
+            feedback_invert: false,
+            feedback_rise_limit: 0.0,
+            smoothness: None,
+            slider_easing: 0.05,
+            center_when_idle: false,
             mapping: Mapping::default(),
             net_sock_addr: "127.0.0.1:16027".into(),
+            net_hold_last: false,
+            net_lock_sender: false,
+            net_poll_rate: 0,
+            net_max_drain: 64,
+            panic_key: "F12".into(),
+            pause_when_unfocused: false,
+            output_ramp: 0.5,
+            startup_delay_ms: 0,
+            output_center_offset: 0.0,
             device_resolution: 32768,
+            device_smoothing: false,
+            bindings: Bindings::default(),
+            lean_output: false,
+            lean_axis: AuxAxis::Z,
+            horn_axis_output: false,
+            horn_axis: AuxAxis::Rz,
             device_name: "G29 Driving Force Racing Wheel [PS3]".into(),
             device_vendor: 0x46D,
             device_product: 0xC24F,
             device_version: 0x3,
+            vigem_scale: 1.0,
+            vigem_invert: false,
+            vigem_max_rate: 0,
+            keys_left: "Left".into(),
+            keys_right: "Right".into(),
+            keys_deadzone: 0.02,
+            keys_saturation: 0.25,
+            mouse_pixels_per_degree: 5.0,
+            mouse_deadzone: 0.0,
+            wheel_base_image: None,
+            wheel_inner_image: None,
             preferred_tablet: None,
-            #[cfg(target_os = "linux")]
-            source: Source::Evdev,
-            #[cfg(target_os = "windows")]
-            source: Source::Wintab,
-            #[cfg(target_os = "linux")]
-            device: Device::UInput,
-            #[cfg(target_os = "windows")]
-            device: Device::VigemBus,
+            evdev_path: None,
+            evdev_x_axis: None,
+            evdev_y_axis: None,
+            evdev_poll_rate: 0,
+            // Nothing is opened until the user picks a source and device.
+            source: Source::None,
+            merge_source: Source::None,
+            merge_policy: MergePolicy::Buttons,
+            device: Device::None,
         }
     }
 }
@@ -113,12 +410,74 @@ impl Display for Source {
     }
 }
 
+impl Display for MergePolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            MergePolicy::Buttons => "Buttons",
+            MergePolicy::PressureAndButtons => "Pressure and buttons",
+        })
+    }
+}
+
+impl Display for SteeringModel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            SteeringModel::Radial => "Radial",
+            SteeringModel::Planar => "Planar",
+        })
+    }
+}
+
+impl Display for LockBehavior {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            LockBehavior::HardStop => "Hard stop",
+            LockBehavior::Bounce => "Bounce",
+        })
+    }
+}
+
+impl Display for GamePreset {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            GamePreset::RacingSim => "Racing sims (Logitech wheel)",
+            GamePreset::TruckSim => "Euro/American Truck Simulator",
+            GamePreset::Arcade => "Arcade racers (self-centring)",
+        })
+    }
+}
+
+impl Display for AuxAxis {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            AuxAxis::Y => "Y",
+            AuxAxis::Z => "Z",
+            AuxAxis::Rx => "RX",
+            AuxAxis::Ry => "RY",
+            AuxAxis::Rz => "RZ",
+        })
+    }
+}
+
+impl Display for EraserAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            EraserAction::Steer => "Steer",
+            EraserAction::Horn => "Horn",
+        })
+    }
+}
+
 impl Display for Device {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.write_str(match self {
             Device::None => "Null",
             #[cfg(target_os = "linux")]
             Device::UInput => "Linux uinput",
+            #[cfg(target_os = "linux")]
+            Device::Keys => "Keyboard keys (uinput)",
+            #[cfg(target_os = "linux")]
+            Device::Mouse => "Relative mouse (uinput)",
             #[cfg(target_os = "windows")]
             Device::VigemBus => "ViGEm Bus",
         })
@@ -126,8 +485,262 @@ impl Display for Device {
 }
 
 impl Config {
+    /// Set the fields covered by a game preset, leaving everything else alone.
+    pub fn apply_game_preset(&mut self, preset: GamePreset) {
+        let defaults = Config::default();
+
+        // Every preset presents the default wheel, which most games recognise.
+        self.device_name = defaults.device_name;
+        self.device_vendor = defaults.device_vendor;
+        self.device_product = defaults.device_product;
+        self.device_version = defaults.device_version;
+        self.device_resolution = defaults.device_resolution;
+        // Steering alone on the usual axis, turning the usual way.
+        self.output_center_offset = 0.0;
+        self.lean_output = false;
+        self.lean_axis = defaults.lean_axis;
+        self.horn_axis_output = false;
+        self.horn_axis = defaults.horn_axis;
+        self.vigem_scale = 1.0;
+        self.vigem_invert = false;
+        self.feedback_invert = false;
+        self.spring_exp = 1.0;
+
+        match preset {
+            GamePreset::RacingSim => {
+                self.range = 900.0;
+                self.spring = 0.0;
+                // Pen buttons on the shift paddles.
+                self.bindings = Bindings {
+                    pen_buttons: ["ButtonTR".into(), "ButtonTL".into(), String::new()],
+                    ..defaults.bindings
+                };
+            }
+            GamePreset::TruckSim => {
+                self.range = 1800.0;
+                self.spring = 0.0;
+                // Pen buttons on the indicators, which the games leave unbound.
+                self.bindings = Bindings {
+                    pen_buttons: ["ButtonTL".into(), "ButtonTR".into(), String::new()],
+                    ..defaults.bindings
+                };
+            }
+            GamePreset::Arcade => {
+                self.range = 540.0;
+                self.spring = 5.0;
+                self.bindings = Bindings {
+                    pen_buttons: ["ButtonSouth".into(), "ButtonEast".into(), String::new()],
+                    eraser: "ButtonNorth".into(),
+                    ..defaults.bindings
+                };
+            }
+        }
+    }
+
+    /// Names of the settings which applying a preset would overwrite, where they
+    /// have been changed from their defaults.
+    pub fn game_preset_overwrites(&self, preset: GamePreset) -> Vec<&'static str> {
+        fn overwritten<T: PartialEq>(current: &T, preset: &T, default: &T) -> bool {
+            current != preset && current != default
+        }
+
+        let defaults = Config::default();
+        let mut after = self.clone();
+        after.apply_game_preset(preset);
+
+        let identity = |c: &Config| {
+            (
+                c.device_name.clone(),
+                c.device_vendor,
+                c.device_product,
+                c.device_version,
+                c.device_resolution,
+            )
+        };
+        let axes = |c: &Config| {
+            (
+                c.output_center_offset,
+                c.lean_output,
+                c.lean_axis,
+                c.horn_axis_output,
+                c.horn_axis,
+            )
+        };
+        let inversion = |c: &Config| (c.vigem_scale, c.vigem_invert, c.feedback_invert);
+        let spring = |c: &Config| (c.spring, c.spring_exp);
+
+        [
+            (
+                "range",
+                overwritten(&self.range, &after.range, &defaults.range),
+            ),
+            (
+                "spring",
+                overwritten(&spring(self), &spring(&after), &spring(&defaults)),
+            ),
+            (
+                "device identity",
+                overwritten(&identity(self), &identity(&after), &identity(&defaults)),
+            ),
+            (
+                "axes",
+                overwritten(&axes(self), &axes(&after), &axes(&defaults)),
+            ),
+            (
+                "inversion",
+                overwritten(&inversion(self), &inversion(&after), &inversion(&defaults)),
+            ),
+            (
+                "bindings",
+                overwritten(&self.bindings, &after.bindings, &defaults.bindings),
+            ),
+        ]
+        .into_iter()
+        .filter_map(|(name, overwritten)| overwritten.then_some(name))
+        .collect()
+    }
+
+    /// Derive the individual smoothing settings from `smoothness`, so they can be
+    /// traded against latency together. They can still be edited afterwards.
+    /// Nothing changes until a smoothness has been chosen.
+    pub fn apply_smoothness(&mut self) {
+        /// Slider easing at full smoothness.
+        const MAX_SLIDER_EASING: f32 = 0.15;
+        /// Time (in seconds) for feedback to rise to full torque at full smoothness.
+        const MAX_FEEDBACK_RISE_TIME: f32 = 0.25;
+
+        let Some(s) = self.smoothness else {
+            return;
+        };
+        let s = s.clamp(0.0, 1.0);
+
+        self.slider_easing = MAX_SLIDER_EASING * s;
+        self.device_smoothing = s >= 0.5;
+        self.feedback_slew = if s > 0.0 {
+            self.max_torque.abs() / (MAX_FEEDBACK_RISE_TIME * s)
+        } else {
+            0.0
+        };
+    }
+
+    /// How many times device events are polled per physics update. A source
+    /// polled faster than the updates is read in the same slots.
+    pub fn polls_per_update(&self) -> u32 {
+        let source_polls = self.source_poll_rate().div_ceil(self.update_frequency);
+        self.event_poll_frequency
+            .div_ceil(self.update_frequency)
+            .max(source_polls)
+            .max(1)
+    }
+
+    /// Times per second the current source is polled.
+    pub fn source_poll_rate(&self) -> u32 {
+        let rate = match self.source {
+            Source::Net => self.net_poll_rate,
+            #[cfg(target_os = "linux")]
+            Source::Evdev => self.evdev_poll_rate,
+            _ => 0,
+        };
+
+        if rate == 0 {
+            self.update_frequency
+        } else {
+            rate.min(MAX_SOURCE_POLL_RATE)
+        }
+    }
+
     /// Half angle range in radians.
     pub fn half_range_rad(&self) -> f32 {
         0.5 * self.range.to_radians()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn smoothness_changes_nothing_until_chosen() {
+        let mut config = Config::default();
+        config.apply_smoothness();
+
+        let defaults = Config::default();
+        assert_eq!(config.slider_easing, defaults.slider_easing);
+        assert_eq!(config.feedback_slew, defaults.feedback_slew);
+        assert_eq!(config.device_smoothing, defaults.device_smoothing);
+    }
+
+    #[test]
+    fn smoothness_sets_the_individual_settings() {
+        let mut config = Config {
+            smoothness: Some(1.0),
+            max_torque: 2.0,
+            ..Default::default()
+        };
+        config.apply_smoothness();
+        assert_eq!(config.slider_easing, 0.15);
+        assert_eq!(config.feedback_slew, 8.0);
+        assert!(config.device_smoothing);
+
+        config.smoothness = Some(0.0);
+        config.apply_smoothness();
+        assert_eq!(config.slider_easing, 0.0);
+        assert_eq!(config.feedback_slew, 0.0);
+        assert!(!config.device_smoothing);
+    }
+
+    #[test]
+    fn presets_overwrite_nothing_left_at_defaults() {
+        for preset in GamePreset::ALL {
+            assert!(Config::default().game_preset_overwrites(preset).is_empty());
+        }
+    }
+
+    #[test]
+    fn presets_report_overwriting_custom_tuning() {
+        let config = Config {
+            range: 720.0,
+            lean_output: true,
+            feedback_invert: true,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            config.game_preset_overwrites(GamePreset::RacingSim),
+            ["range", "axes", "inversion"]
+        );
+    }
+
+    #[test]
+    fn reapplying_a_preset_overwrites_nothing() {
+        for preset in GamePreset::ALL {
+            let mut config = Config {
+                range: 720.0,
+                ..Default::default()
+            };
+            config.apply_game_preset(preset);
+            assert!(config.game_preset_overwrites(preset).is_empty());
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn preset_bindings_are_buttons() {
+        use crate::device::keys::parse_button_name;
+
+        for preset in GamePreset::ALL {
+            let mut config = Config::default();
+            config.apply_game_preset(preset);
+
+            let bindings = &config.bindings;
+            for name in bindings
+                .pen_buttons
+                .iter()
+                .chain([&bindings.horn, &bindings.eraser, &bindings.clutch])
+                .filter(|name| !name.is_empty())
+            {
+                assert!(parse_button_name(name).is_ok(), "{preset}: {name}");
+            }
+        }
+    }
+}
@@ -1,16 +1,33 @@
 use anyhow::{Context, Result};
-use log::{debug, error, info};
+use log::{debug, error, info, warn};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
+use std::thread::sleep;
+use std::time::{Duration, Instant};
 
-use crate::device::create_device;
+use crate::config;
+use crate::device::{DummyDevice, create_device};
+use crate::pen::{RawPen, SECOND_CONTACT_BIT, Tool};
 use crate::source::create_source;
 use crate::{state::State, timer::Timer};
 
+/// How many times device creation is attempted at startup before giving up.
+const STARTUP_DEVICE_ATTEMPTS: u32 = 4;
+/// Most physics steps run in one update when catching up with a fixed timestep,
+/// so that a long stall doesn't make the wheel leap.
+const MAX_CATCH_UP_STEPS: u32 = 4;
+
 pub fn controller(state: Arc<Mutex<State>>, quit_flag: Arc<AtomicBool>) {
-    let mut update_frequency = state.lock().unwrap().config.update_frequency;
+    create_startup_device(&state, &quit_flag);
+
+    let (mut update_frequency, mut polls_per_update) = {
+        let config = &state.lock().unwrap().config;
+        (config.update_frequency, config.polls_per_update())
+    };
     info!("Using {update_frequency} Hz rate.");
-    let mut timer = Timer::new(update_frequency);
+    let mut timer = Timer::new(update_frequency * polls_per_update);
+    let mut polls = 0;
+    let mut last_wakeup = Instant::now();
 
     loop {
         if quit_flag.load(Ordering::Acquire) {
@@ -20,16 +37,37 @@ pub fn controller(state: Arc<Mutex<State>>, quit_flag: Arc<AtomicBool>) {
 
         let mut locked = state.lock().unwrap();
 
-        if let Err(err) = update(&mut locked).context("Error during controller tick.") {
-            error!("Controller error: {err}");
-            locked.last_error = Some(err);
+        let now = Instant::now();
+        locked.record_tick_interval(now - last_wakeup);
+        last_wakeup = now;
+
+        // Device events, and sources faster than the updates, are serviced
+        // between updates so that slow update rates do not starve them.
+        polls += 1;
+        if polls >= polls_per_update {
+            polls = 0;
+
+            if let Err(err) = update(&mut locked).context("Error during controller tick.") {
+                error!("Controller error: {err}");
+                locked.last_error = Some(err);
+            }
+        } else {
+            poll_between_updates(&mut locked);
         }
 
         let current_update_frequency = locked.config.update_frequency;
-        if current_update_frequency != update_frequency {
+        let current_polls_per_update = locked.config.polls_per_update();
+        if current_update_frequency != update_frequency
+            || current_polls_per_update != polls_per_update
+        {
             update_frequency = current_update_frequency;
-            timer = Timer::new(update_frequency);
-            info!("Now updating at {update_frequency} Hz.");
+            polls_per_update = current_polls_per_update;
+            polls = 0;
+            timer = Timer::new(update_frequency * polls_per_update);
+            locked.tick_intervals.clear();
+            info!(
+                "Now updating at {update_frequency} Hz, polling events and the source up to {polls_per_update}x per update."
+            );
         }
 
         // unlock before waiting
@@ -38,6 +76,66 @@ pub fn controller(state: Arc<Mutex<State>>, quit_flag: Arc<AtomicBool>) {
     }
 }
 
+/// Wait for the configured startup delay, then create the device, retrying with
+/// backoff in case its driver is not ready yet.
+fn create_startup_device(state: &Mutex<State>, quit_flag: &AtomicBool) {
+    let delay = Duration::from_millis(state.lock().unwrap().config.startup_delay_ms.into());
+    if !delay.is_zero() {
+        info!("Waiting {delay:?} before creating the device.");
+        if !sleep_unless_quit(delay, quit_flag) {
+            return;
+        }
+    }
+
+    let mut backoff = Duration::from_millis(500);
+    for attempt in 1..=STARTUP_DEVICE_ATTEMPTS {
+        let result = {
+            let mut locked = state.lock().unwrap();
+            if !locked.reset_device {
+                return;
+            }
+
+            reset_device(&mut locked)
+        };
+
+        let Err(err) = result else {
+            return;
+        };
+
+        if attempt == STARTUP_DEVICE_ATTEMPTS {
+            error!("Controller error: {err}");
+            state.lock().unwrap().last_error = Some(err);
+            return;
+        }
+
+        warn!("Device creation failed (attempt {attempt}), retrying in {backoff:?}: {err}");
+        if !sleep_unless_quit(backoff, quit_flag) {
+            return;
+        }
+        backoff *= 2;
+
+        state.lock().unwrap().reset_device = true;
+    }
+}
+
+/// Sleep for the given duration, waking early if the controller is stopping.
+/// Returns false if it was asked to stop.
+fn sleep_unless_quit(duration: Duration, quit_flag: &AtomicBool) -> bool {
+    const STEP: Duration = Duration::from_millis(50);
+
+    let end = Instant::now() + duration;
+    while !quit_flag.load(Ordering::Acquire) {
+        let now = Instant::now();
+        if now >= end {
+            return true;
+        }
+
+        sleep(STEP.min(end - now));
+    }
+
+    false
+}
+
 pub fn update(state: &mut State) -> Result<()> {
     if state.reset_source {
         reset_source(state)?;
@@ -47,37 +145,342 @@ pub fn update(state: &mut State) -> Result<()> {
         reset_device(state)?;
     }
 
-    if let Some(Some(ref raw_pen)) = state.source.as_mut().map(|s| s.get()) {
-        let pen = state.config.mapping.pen(raw_pen.clone());
-        state.pen = Some(pen);
+    update_focus_pause(state);
+    if state.paused || state.focus_paused {
+        return hold_paused(state);
+    }
+
+    if source_poll_due(state, true) {
+        poll_source(state);
     }
 
+    recenter_from_button(state);
+
+    let dt = 1.0 / state.config.update_frequency as f32;
+    let pen = state.pen_override.clone().or_else(|| state.pen.clone());
+    let steps = physics_steps(state, Duration::from_secs_f32(dt));
     state.wheel.update(
         state.device.as_mut(),
         &state.config,
-        state.pen_override.clone().or_else(|| state.pen.clone()),
-        1.0 / state.config.update_frequency as f32,
+        pen,
+        dt,
+        steps,
+        state.freeze_physics,
     );
 
+    capture_physics(state);
+    ramp_output(state);
+    apply_second_contact_horn(state);
+    apply_bindings(state);
+    apply_lean(state);
+
     if let Some(device) = &mut state.device {
         device.apply().context("error applying device")?;
+        state.last_device_apply = Some(Instant::now());
         device.handle_events();
     }
 
     Ok(())
 }
 
+/// Service the device, and the source if it is due, in a slot between updates.
+fn poll_between_updates(state: &mut State) {
+    if !state.paused && !state.focus_paused && source_poll_due(state, false) {
+        poll_source(state);
+    }
+
+    if let Some(device) = &mut state.device {
+        device.handle_events();
+    }
+}
+
+/// Read the source once, keeping its sample if it has one.
+fn poll_source(state: &mut State) {
+    let Some(Some(ref raw_pen)) = state.source.as_mut().map(|s| s.get()) else {
+        return;
+    };
+
+    if !raw_pen.stale {
+        state.last_source_sample = Some(Instant::now());
+        dump_input(state, raw_pen);
+    }
+
+    let physical_size = state.source.as_ref().and_then(|s| s.physical_size());
+    let pen = state.config.mapping.pen(raw_pen.clone(), physical_size);
+    state.pen = Some(pen);
+    state.raw_pen = Some(raw_pen.clone());
+}
+
+/// Log a fresh sample to the input dump, stopping it if the file can't be written.
+fn dump_input(state: &mut State, raw_pen: &RawPen) {
+    let Some(dump) = &mut state.input_dump else {
+        return;
+    };
+
+    if let Err(err) = dump.write(raw_pen) {
+        error!("Stopping input dump to {}: {err}", dump.path().display());
+        state.input_dump = None;
+    }
+}
+
+/// Record the wheel for a running physics capture, saving it once it is over.
+fn capture_physics(state: &mut State) {
+    let Some(capture) = &mut state.physics_capture else {
+        return;
+    };
+
+    if !capture.record(&state.wheel) {
+        return;
+    }
+
+    // Writing the file can take a while, and the state is locked during an update.
+    let Some(capture) = state.physics_capture.take() else {
+        return;
+    };
+    std::thread::spawn(move || match capture.save() {
+        Ok(()) => info!("Saved physics capture to {}", capture.path().display()),
+        Err(err) => error!("Could not save physics capture: {err}"),
+    });
+}
+
+/// How many physics steps of `dt` to run on this update. Normally one, but with
+/// a fixed timestep, however many fit into the real time that has passed.
+fn physics_steps(state: &mut State, dt: Duration) -> u32 {
+    let now = Instant::now();
+    let last = state.last_physics_update.replace(now);
+    if !state.config.fixed_timestep {
+        state.physics_time_debt = Duration::ZERO;
+        return 1;
+    }
+
+    let Some(last) = last else {
+        return 1;
+    };
+
+    state.physics_time_debt += now - last;
+    let mut steps = 0;
+    while state.physics_time_debt >= dt && steps < MAX_CATCH_UP_STEPS {
+        state.physics_time_debt -= dt;
+        steps += 1;
+    }
+
+    // Give up on time that can't be caught up with rather than carrying it over.
+    if state.physics_time_debt >= dt {
+        state.physics_time_debt = Duration::ZERO;
+    }
+
+    steps
+}
+
+/// Whether the source should be polled now, on an update or in a slot between
+/// them, given its own poll rate. At most one poll is due at a time, so a fast
+/// source is read at its own cadence across the slots rather than in bursts.
+fn source_poll_due(state: &mut State, updating: bool) -> bool {
+    let rate = state.config.source_poll_rate();
+    if rate == state.config.update_frequency {
+        return updating;
+    }
+
+    let period = Duration::from_secs_f64(1.0 / rate as f64);
+    let now = Instant::now();
+    let next = state.next_source_poll.unwrap_or(now);
+    if now < next {
+        return false;
+    }
+
+    // Keep to the source's cadence, but do not try to catch up after a stall.
+    state.next_source_poll = Some(if now > next + period {
+        now + period
+    } else {
+        next + period
+    });
+
+    true
+}
+
+/// Pause while the GUI window is unfocused, if configured to, easing back in on focus.
+fn update_focus_pause(state: &mut State) {
+    let pause = state.config.pause_when_unfocused && state.gui_unfocused;
+    if pause == state.focus_paused {
+        return;
+    }
+
+    state.focus_paused = pause;
+    if pause {
+        info!("Window unfocused; output neutralised.");
+        state.wheel.honking = false;
+        state.wheel.dragging = false;
+    } else {
+        info!("Window focused; resuming.");
+        state.output_ramp_start = Some(Instant::now());
+    }
+}
+
+/// Hold the output neutral while paused.
+fn hold_paused(state: &mut State) -> Result<()> {
+    // Don't count the pause as time the physics has to catch up on.
+    state.last_physics_update = None;
+
+    let Some(device) = &mut state.device else {
+        return Ok(());
+    };
+
+    // Keep applying, as smoothed devices take a few updates to settle.
+    device.set_wheel(0.0);
+    device.set_horn(false);
+    device.apply().context("error applying device")?;
+    device.handle_events();
+
+    Ok(())
+}
+
+/// Zero the wheel when the configured pen button is pressed. Only the press
+/// itself counts, so holding the button does not pin the wheel against physics.
+fn recenter_from_button(state: &mut State) {
+    let Some(bit) = state.config.recenter_button else {
+        state.recenter_held = false;
+        return;
+    };
+
+    let held = state
+        .pen
+        .as_ref()
+        .is_some_and(|pen| pen.buttons & (1 << bit) != 0);
+
+    if held && !state.recenter_held {
+        debug!("Recentring from pen button.");
+        state.wheel.angle = 0.0;
+        state.wheel.velocity = 0.0;
+    }
+
+    state.recenter_held = held;
+}
+
+/// Sound the horn while a second contact touches, without interrupting steering.
+fn apply_second_contact_horn(state: &mut State) {
+    if !state.config.second_contact_horn || !state.config.horn_enabled {
+        return;
+    }
+
+    let Some(device) = &mut state.device else {
+        return;
+    };
+
+    let contact = state
+        .pen_override
+        .as_ref()
+        .or(state.pen.as_ref())
+        .is_some_and(|pen| pen.buttons & (1 << SECOND_CONTACT_BIT) != 0);
+
+    device.set_horn(state.wheel.honking || contact);
+}
+
+/// Pass the pen buttons and eraser on to the device, for it to press whatever they are bound to.
+fn apply_bindings(state: &mut State) {
+    let Some(device) = &mut state.device else {
+        return;
+    };
+
+    let pen = state.pen_override.as_ref().or(state.pen.as_ref());
+    let buttons = pen.map_or(0, |pen| pen.buttons);
+    let erasing = pen.is_some_and(|pen| {
+        pen.tool == Tool::Eraser && pen.pressure > state.config.pressure_threshold
+    });
+    // Pressing harder than usual holds the clutch, while still steering.
+    let clutch = state.config.clutch_pressure > 0
+        && pen.is_some_and(|pen| {
+            pen.tool == Tool::Pen && pen.pressure > state.config.clutch_pressure
+        });
+
+    device.set_pen_buttons(buttons);
+    device.set_eraser(erasing);
+    device.set_clutch(clutch);
+}
+
+/// Send how far the pen leans to either side, from its tilt, on the device's extra axis.
+fn apply_lean(state: &mut State) {
+    if !state.config.lean_output {
+        return;
+    }
+
+    let Some(device) = &mut state.device else {
+        return;
+    };
+
+    let tilt = state
+        .pen_override
+        .as_ref()
+        .or(state.pen.as_ref())
+        .and_then(|pen| pen.tilt);
+    let lean = tilt.map_or(0.0, |(x, y)| x.hypot(y).min(1.0).copysign(x));
+
+    device.set_aux_axis(lean);
+}
+
+/// Ease the output in after the device was created, so that a connected game
+/// does not see the axis jump straight to the current angle.
+fn ramp_output(state: &mut State) {
+    let Some(start) = state.output_ramp_start else {
+        return;
+    };
+
+    let progress = if state.config.output_ramp > 0.0 {
+        start.elapsed().as_secs_f32() / state.config.output_ramp
+    } else {
+        1.0
+    };
+
+    if progress >= 1.0 {
+        state.output_ramp_start = None;
+    }
+
+    if let Some(device) = &mut state.device {
+        let normalised = state.wheel.angle / state.config.half_range_rad();
+        device.set_wheel(normalised * progress.min(1.0));
+    }
+}
+
 fn reset_source(state: &mut State) -> Result<()> {
     debug!("resetting source.");
 
-    state.pen = None;
     state.reset_source = false;
-    state.source = None;
+    state.last_source_sample = None;
+    state.next_source_poll = None;
+
+    // Open the new source while the old one is still around, so that switching
+    // tablets leaves no gap. That fails if both need the same resource, such as
+    // a network port, in which case the old one has to go first.
+    let created = match create_source(&state.config) {
+        Err(err) if state.source.is_some() => {
+            debug!("Could not open source alongside the old one ({err}); retrying.");
+            state.source = None;
+            create_source(&state.config)
+        }
+        created => created,
+    };
 
-    match create_source(&state.config) {
-        Ok(source) => state.source = Some(source),
+    // When only the tablet changes, let go of the wheel rather than forgetting
+    // the pen, so that it carries on from where it was instead of being treated
+    // as idle until the next sample.
+    if state.active_source == state.config.source
+        && let Some(pen) = &mut state.pen
+    {
+        pen.pressure = 0;
+    } else {
+        state.pen = None;
+    }
+    state.raw_pen = None;
+
+    match created {
+        Ok(source) => {
+            state.source = Some(source);
+            state.active_source = state.config.source;
+        }
         Err(err) => {
             error!("Failed to create source!");
+            state.pen = None;
+            state.source = None;
+            state.active_source = config::Source::None;
             return Err(err);
         }
     }
@@ -91,14 +494,377 @@ fn reset_device(state: &mut State) -> Result<()> {
     state.pen = None;
     state.reset_device = false;
     state.device = None;
+    state.active_device = config::Device::None;
+    state.device_fallback = false;
+    state.last_device_apply = None;
 
     match create_device(&state.config).context("Could not create device.") {
-        Ok(device) => state.device = Some(device),
+        Ok(device) => {
+            state.device = Some(device);
+            state.active_device = state.config.device;
+            state.output_ramp_start = Some(Instant::now());
+
+            if state.config.feedback_invert {
+                info!("Force feedback is inverted.");
+            }
+        }
         Err(err) => {
-            error!("Failed to create device!");
+            error!("Failed to create device! Falling back to no output.");
+            state.device = Some(Box::new(DummyDevice));
+            state.device_fallback = true;
             return Err(err);
         }
     }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::mpsc::Sender;
+
+    use super::*;
+    use crate::{device::Device, source::channel::ChannelSource};
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    enum Call {
+        Wheel(f32),
+        Horn(bool),
+        Apply,
+    }
+
+    /// Device which records every call made to it, to be inspected by the test.
+    struct RecordingDevice(Arc<Mutex<Vec<Call>>>);
+
+    impl Device for RecordingDevice {
+        fn get_feedback(&self) -> Option<f32> {
+            None
+        }
+
+        fn set_wheel(&mut self, angle: f32) {
+            self.0.lock().unwrap().push(Call::Wheel(angle));
+        }
+
+        fn set_horn(&mut self, honking: bool) {
+            self.0.lock().unwrap().push(Call::Horn(honking));
+        }
+
+        fn apply(&mut self) -> Result<()> {
+            self.0.lock().unwrap().push(Call::Apply);
+            Ok(())
+        }
+
+        fn handle_events(&mut self) {}
+    }
+
+    struct Pipeline {
+        state: State,
+        pen: Sender<RawPen>,
+        calls: Arc<Mutex<Vec<Call>>>,
+    }
+
+    impl Pipeline {
+        fn new() -> Self {
+            let (source, pen) = ChannelSource::new();
+            let calls = Arc::new(Mutex::new(Vec::new()));
+
+            let state = State {
+                reset_source: false,
+                reset_device: false,
+                source: Some(Box::new(source)),
+                device: Some(Box::new(RecordingDevice(calls.clone()))),
+                ..Default::default()
+            };
+
+            Self { state, pen, calls }
+        }
+
+        /// Send a pen sample and run one update.
+        fn step(&mut self, x: f32, y: f32, pressure: u32) {
+            self.pen
+                .send(RawPen {
+                    x,
+                    y,
+                    pressure,
+                    ..Default::default()
+                })
+                .unwrap();
+            update(&mut self.state).unwrap();
+        }
+
+        fn last_wheel(&self) -> Option<f32> {
+            self.calls
+                .lock()
+                .unwrap()
+                .iter()
+                .rev()
+                .find_map(|call| match call {
+                    Call::Wheel(angle) => Some(*angle),
+                    _ => None,
+                })
+        }
+
+        fn horn_events(&self) -> Vec<bool> {
+            self.calls
+                .lock()
+                .unwrap()
+                .iter()
+                .filter_map(|call| match call {
+                    Call::Horn(honking) => Some(*honking),
+                    _ => None,
+                })
+                .collect()
+        }
+    }
+
+    const PRESSED: u32 = 4000;
+
+    #[test]
+    fn circling_the_pen_turns_the_wheel() {
+        let mut pipeline = Pipeline::new();
+
+        let steps = 10;
+        let sweep = 1.0;
+        for i in 0..=steps {
+            let theta = sweep * i as f32 / steps as f32;
+            pipeline.step(0.8 * theta.sin(), 0.8 * theta.cos(), PRESSED);
+        }
+
+        let angle = pipeline.state.wheel.angle;
+        assert!((angle - sweep).abs() < 1e-3, "angle {angle}");
+
+        let half_range = pipeline.state.config.half_range_rad();
+        let sent = pipeline.last_wheel().unwrap();
+        assert!((sent - angle / half_range).abs() < 1e-4, "sent {sent}");
+        assert!(pipeline.horn_events().iter().all(|honking| !honking));
+        assert_eq!(pipeline.calls.lock().unwrap().last(), Some(&Call::Apply));
+    }
+
+    #[test]
+    fn pressing_the_centre_sounds_the_horn() {
+        let mut pipeline = Pipeline::new();
+
+        pipeline.step(0.0, 0.05, PRESSED);
+        assert!(pipeline.state.wheel.honking);
+        assert_eq!(pipeline.horn_events(), [true]);
+
+        pipeline.step(0.0, 0.05, 0);
+        assert!(!pipeline.state.wheel.honking);
+        assert_eq!(pipeline.horn_events(), [true, false]);
+        assert_eq!(pipeline.state.wheel.angle, 0.0);
+    }
+
+    #[test]
+    fn pausing_sends_a_neutral_frame() {
+        let mut pipeline = Pipeline::new();
+
+        for i in 0..=5 {
+            let theta = 0.1 * i as f32;
+            pipeline.step(0.8 * theta.sin(), 0.8 * theta.cos(), PRESSED);
+        }
+        assert!(pipeline.last_wheel().unwrap() != 0.0);
+
+        pipeline.state.pause();
+        pipeline.step(0.8, 0.8, PRESSED);
+
+        let calls = pipeline.calls.lock().unwrap();
+        assert_eq!(
+            calls[calls.len() - 3..],
+            [Call::Wheel(0.0), Call::Horn(false), Call::Apply]
+        );
+    }
+
+    #[test]
+    fn second_contact_sounds_the_horn_while_steering() {
+        let mut pipeline = Pipeline::new();
+        pipeline.state.config.second_contact_horn = true;
+
+        let second_contact = |buttons| RawPen {
+            x: 0.8,
+            y: 0.0,
+            pressure: PRESSED,
+            buttons,
+            ..Default::default()
+        };
+        pipeline.pen.send(second_contact(0)).unwrap();
+        update(&mut pipeline.state).unwrap();
+        pipeline
+            .pen
+            .send(second_contact(1 << SECOND_CONTACT_BIT))
+            .unwrap();
+        update(&mut pipeline.state).unwrap();
+
+        assert!(!pipeline.state.wheel.honking);
+        assert_eq!(pipeline.horn_events().last(), Some(&true));
+
+        pipeline.pen.send(second_contact(0)).unwrap();
+        update(&mut pipeline.state).unwrap();
+        assert_eq!(pipeline.horn_events().last(), Some(&false));
+    }
+
+    #[test]
+    fn second_contact_is_ignored_unless_enabled() {
+        let mut pipeline = Pipeline::new();
+
+        pipeline
+            .pen
+            .send(RawPen {
+                x: 0.8,
+                pressure: PRESSED,
+                buttons: 1 << SECOND_CONTACT_BIT,
+                ..Default::default()
+            })
+            .unwrap();
+        update(&mut pipeline.state).unwrap();
+
+        assert!(pipeline.horn_events().iter().all(|honking| !honking));
+    }
+
+    const STEP: Duration = Duration::from_millis(10);
+
+    fn fixed_timestep_since(elapsed: Duration) -> State {
+        State {
+            config: config::Config {
+                fixed_timestep: true,
+                ..Default::default()
+            },
+            last_physics_update: Some(Instant::now() - elapsed),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn one_physics_step_without_fixed_timestep() {
+        let mut state = fixed_timestep_since(Duration::from_millis(35));
+        state.config.fixed_timestep = false;
+
+        assert_eq!(physics_steps(&mut state, STEP), 1);
+        assert_eq!(state.physics_time_debt, Duration::ZERO);
+    }
+
+    #[test]
+    fn fixed_timestep_runs_the_steps_that_fit() {
+        let mut state = fixed_timestep_since(Duration::from_millis(35));
+
+        assert_eq!(physics_steps(&mut state, STEP), 3);
+        // About 5 ms is left over, plus however long the test took.
+        let debt = state.physics_time_debt;
+        assert!(debt >= Duration::from_millis(5) && debt < STEP, "{debt:?}");
+
+        // The leftover counts towards the next update.
+        state.last_physics_update = Some(Instant::now() - Duration::from_millis(6));
+        assert_eq!(physics_steps(&mut state, STEP), 1);
+    }
+
+    #[test]
+    fn fixed_timestep_starts_with_one_step() {
+        let mut state = fixed_timestep_since(Duration::ZERO);
+        state.last_physics_update = None;
+
+        assert_eq!(physics_steps(&mut state, STEP), 1);
+        assert!(state.last_physics_update.is_some());
+    }
+
+    #[test]
+    fn catching_up_is_capped() {
+        let mut state = fixed_timestep_since(Duration::from_millis(100));
+
+        assert_eq!(physics_steps(&mut state, STEP), MAX_CATCH_UP_STEPS);
+        // The time which couldn't be caught up with is dropped.
+        assert_eq!(state.physics_time_debt, Duration::ZERO);
+    }
+
+    /// Circle the pen with the given real time before each update, then let go and
+    /// leave the wheel to spin for a while. Returns where it ends up.
+    fn spin_after_updates_spaced(gaps: &[Duration]) -> f32 {
+        let mut pipeline = Pipeline::new();
+        pipeline.state.config.fixed_timestep = true;
+        let step = Duration::from_secs_f32(1.0 / pipeline.state.config.update_frequency as f32);
+
+        for (i, gap) in gaps.iter().enumerate() {
+            let theta = 0.1 * i as f32;
+            pipeline.state.last_physics_update = Some(Instant::now() - *gap);
+            pipeline.step(0.8 * theta.sin(), 0.8 * theta.cos(), PRESSED);
+        }
+
+        for _ in 0..20 {
+            pipeline.state.last_physics_update = Some(Instant::now() - step);
+            pipeline.step(0.0, 0.8, 0);
+        }
+
+        pipeline.state.wheel.angle
+    }
+
+    #[test]
+    fn uneven_updates_turn_the_wheel_the_same() {
+        let step = Duration::from_secs_f32(1.0 / config::Config::default().update_frequency as f32);
+        let even = [step; 8];
+        // Alternately too early for a physics step and late enough for two.
+        let uneven: Vec<_> = (0..8)
+            .map(|i| if i % 2 == 0 { step / 2 } else { step * 3 / 2 })
+            .collect();
+
+        let angle = spin_after_updates_spaced(&even);
+        assert!(angle != 0.0);
+        assert_eq!(spin_after_updates_spaced(&uneven), angle);
+    }
+
+    fn net_polled_at(rate: u32) -> State {
+        State {
+            config: config::Config {
+                source: config::Source::Net,
+                update_frequency: 40,
+                net_poll_rate: rate,
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn source_follows_the_update_rate_by_default() {
+        let mut state = net_polled_at(0);
+        assert_eq!(state.config.source_poll_rate(), 40);
+        assert!(source_poll_due(&mut state, true));
+        assert!(source_poll_due(&mut state, true));
+        assert!(!source_poll_due(&mut state, false));
+    }
+
+    #[test]
+    fn faster_source_is_polled_between_updates() {
+        let mut state = net_polled_at(100);
+        state.config.event_poll_frequency = 0;
+
+        // 100 Hz over 40 Hz updates needs three slots per update.
+        assert_eq!(state.config.polls_per_update(), 3);
+
+        // However late, only one poll is due, and missed ones aren't made up in a burst.
+        state.next_source_poll = Some(Instant::now() - Duration::from_millis(25));
+        assert!(source_poll_due(&mut state, false));
+        assert!(!source_poll_due(&mut state, true));
+        assert!(!source_poll_due(&mut state, false));
+
+        // A poll 5 ms late is due, and the next stays on the cadence, 5 ms away.
+        state.next_source_poll = Some(Instant::now() - Duration::from_millis(5));
+        assert!(source_poll_due(&mut state, true));
+        assert!(!source_poll_due(&mut state, false));
+    }
+
+    #[test]
+    fn slower_source_is_skipped_between_polls() {
+        let mut state = net_polled_at(10);
+
+        assert!(source_poll_due(&mut state, true));
+        assert!(!source_poll_due(&mut state, true));
+        assert!(!source_poll_due(&mut state, false));
+    }
+
+    #[test]
+    fn poll_rate_is_only_capped_at_the_maximum() {
+        assert_eq!(net_polled_at(250).config.source_poll_rate(), 250);
+        assert_eq!(
+            net_polled_at(10_000).config.source_poll_rate(),
+            config::MAX_SOURCE_POLL_RATE
+        );
+    }
+}
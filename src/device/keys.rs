@@ -0,0 +1,236 @@
+use std::{
+    fmt::Debug,
+    fs::{File, OpenOptions},
+    os::unix::fs::OpenOptionsExt,
+    time::{Duration, Instant},
+};
+
+use crate::{config::Config, device::Device, math};
+use anyhow::{Context, Result, bail};
+use input_linux::{
+    EventKind, EventTime, InputEvent, InputId, Key, KeyEvent, KeyState, SynchronizeEvent,
+    SynchronizeKind, UInputHandle, sys::BUS_VIRTUAL,
+};
+use log::{debug, error, info};
+use nix::libc::O_NONBLOCK;
+
+const ZERO: EventTime = EventTime::new(0, 0);
+
+/// Length of one press/release cycle when the wheel is only partially turned.
+const CYCLE_PERIOD: Duration = Duration::from_millis(100);
+
+/// Virtual keyboard which taps keys according to how far the wheel is turned.
+///
+/// Past the deadzone, the key is held for a fraction of every cycle, which
+/// ramps up until the key is held down continuously at the saturation point.
+pub struct KeysDevice {
+    handle: UInputHandle<File>,
+    left: Key,
+    right: Key,
+    deadzone: f32,
+    saturation: f32,
+    wheel: f32,
+    held: Option<Key>,
+    cycle_start: Instant,
+}
+
+impl KeysDevice {
+    pub fn new(config: &Config) -> Result<Self> {
+        let left = parse_key_name(&config.keys_left)?;
+        let right = parse_key_name(&config.keys_right)?;
+
+        if left == right {
+            bail!("Left and right keys must be different!");
+        }
+
+        if config.keys_saturation <= config.keys_deadzone {
+            bail!("Key saturation must be greater than the deadzone!");
+        }
+
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .custom_flags(O_NONBLOCK)
+            .open("/dev/uinput")
+            .context("Could not open uinput file!")?;
+
+        let handle = UInputHandle::new(file);
+
+        handle.set_evbit(EventKind::Key)?;
+        handle.set_keybit(left)?;
+        handle.set_keybit(right)?;
+
+        let id = InputId {
+            bustype: BUS_VIRTUAL,
+            vendor: 0,
+            product: 0,
+            version: 1,
+        };
+
+        debug!("Creating virtual keyboard:\n\tLeft: {left:?}\n\tRight: {right:?}");
+
+        handle.create(&id, b"pen-steer keys", 0, &[])?;
+
+        info!("Initialised!");
+
+        Ok(Self {
+            handle,
+            left,
+            right,
+            deadzone: config.keys_deadzone,
+            saturation: config.keys_saturation,
+            wheel: 0.0,
+            held: None,
+            cycle_start: Instant::now(),
+        })
+    }
+
+    /// Which key, if any, should be down right now.
+    fn wanted_key(&mut self) -> Option<Key> {
+        let deflection = self.wheel.abs();
+        if deflection <= self.deadzone {
+            return None;
+        }
+
+        let duty = math::remap(deflection, self.deadzone, self.saturation, 0.0, 1.0).min(1.0);
+
+        let now = Instant::now();
+        while now - self.cycle_start >= CYCLE_PERIOD {
+            self.cycle_start += CYCLE_PERIOD;
+        }
+
+        let phase = (now - self.cycle_start).as_secs_f32() / CYCLE_PERIOD.as_secs_f32();
+        if phase >= duty {
+            return None;
+        }
+
+        Some(if self.wheel < 0.0 {
+            self.left
+        } else {
+            self.right
+        })
+    }
+}
+
+impl Device for KeysDevice {
+    fn get_feedback(&self) -> Option<f32> {
+        None
+    }
+
+    fn set_wheel(&mut self, angle: f32) {
+        self.wheel = angle.clamp(-1.0, 1.0);
+    }
+
+    fn set_horn(&mut self, _honking: bool) {}
+
+    fn apply(&mut self) -> Result<()> {
+        let wanted = self.wanted_key();
+        if wanted == self.held {
+            return Ok(());
+        }
+
+        // Release, press, and sync: up to three events.
+        let mut events = Vec::with_capacity(3);
+
+        if let Some(key) = self.held {
+            events.push(InputEvent::from(KeyEvent::new(ZERO, key, KeyState::RELEASED)).into_raw());
+        }
+
+        if let Some(key) = wanted {
+            events.push(InputEvent::from(KeyEvent::new(ZERO, key, KeyState::PRESSED)).into_raw());
+        }
+
+        events.push(
+            InputEvent::from(SynchronizeEvent::new(ZERO, SynchronizeKind::Report, 0)).into_raw(),
+        );
+
+        self.held = wanted;
+
+        self.handle
+            .write(&events)
+            .context("could not write events")?;
+
+        Ok(())
+    }
+
+    fn handle_events(&mut self) {}
+
+    fn describe(&self) -> String {
+        format!(
+            "uinput virtual keyboard\n\
+            \tKeys: {:?} / {:?}\n\
+            \tDeadzone: {}, saturation: {}",
+            self.left, self.right, self.deadzone, self.saturation
+        )
+    }
+}
+
+impl Drop for KeysDevice {
+    fn drop(&mut self) {
+        // Release any held key so it doesn't get stuck.
+        self.set_wheel(0.0);
+        if let Err(err) = self.apply() {
+            error!("Could not release keys: {err}");
+        }
+
+        if let Err(err) = self.handle.dev_destroy() {
+            error!("Error occured destroying uinput device: {err}");
+        }
+    }
+}
+
+impl Debug for KeysDevice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("KeysDevice { /* fields */ }")
+    }
+}
+
+/// Look up a key by its name, e.g. `Left` or `A`.
+pub fn parse_key_name(name: &str) -> Result<Key> {
+    let name = name.trim();
+
+    find_key(name, Key::is_key).with_context(|| format!("No such key \"{name}\"."))
+}
+
+/// Look up a gamepad or mouse button by its name, e.g. `ButtonSouth` or `ButtonThumbr`.
+pub fn parse_button_name(name: &str) -> Result<Key> {
+    let name = name.trim();
+
+    find_key(name, Key::is_button).with_context(|| format!("No such button \"{name}\"."))
+}
+
+fn find_key(name: &str, kind: fn(&Key) -> bool) -> Option<Key> {
+    Key::iter().find(|key| kind(key) && format!("{key:?}").eq_ignore_ascii_case(name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Bindings;
+
+    #[test]
+    fn default_bindings_parse() {
+        let bindings = Bindings::default();
+
+        assert_eq!(
+            parse_button_name(&bindings.horn).unwrap(),
+            Key::ButtonThumbr
+        );
+        assert_eq!(
+            parse_button_name(&bindings.clutch).unwrap(),
+            Key::ButtonWest
+        );
+    }
+
+    #[test]
+    fn buttons_and_keys_are_separate() {
+        assert_eq!(
+            parse_button_name(" buttonsouth ").unwrap(),
+            Key::ButtonSouth
+        );
+        assert!(parse_button_name("Left").is_err());
+
+        assert_eq!(parse_key_name("left").unwrap(), Key::Left);
+        assert!(parse_key_name("ButtonSouth").is_err());
+    }
+}
@@ -1,14 +1,18 @@
 #[cfg(target_os = "linux")]
+pub mod keys;
+#[cfg(target_os = "linux")]
+pub mod mouse;
+#[cfg(target_os = "linux")]
 pub mod uinput;
 
 #[cfg(target_os = "windows")]
 pub mod vigem;
 
 use crate::config;
-#[cfg(target_os = "linux")]
-use crate::device::uinput::UInputDevice;
 #[cfg(target_os = "windows")]
 use crate::device::vigem::VigemDevice;
+#[cfg(target_os = "linux")]
+use crate::device::{keys::KeysDevice, mouse::MouseDevice, uinput::UInputDevice};
 
 use anyhow::Result;
 
@@ -19,9 +23,34 @@ pub trait Device: Send + Sync {
 
     fn set_horn(&mut self, honking: bool);
 
+    /// Pen buttons currently held, as a bitfield.
+    fn set_pen_buttons(&mut self, _buttons: u8) {}
+
+    /// Whether the eraser is touching down.
+    fn set_eraser(&mut self, _touching: bool) {}
+
+    /// Whether the pen is pressed hard enough to hold the clutch.
+    fn set_clutch(&mut self, _engaged: bool) {}
+
+    /// Value of the extra analog axis, from -1.0 to 1.0.
+    fn set_aux_axis(&mut self, _value: f32) {}
+
     fn apply(&mut self) -> Result<()>;
 
     fn handle_events(&mut self);
+
+    /// Whether output is reaching the driver, as opposed to waiting to reconnect.
+    fn connected(&self) -> bool {
+        true
+    }
+
+    /// Play a brief, decaying force as if the game had requested one.
+    fn trigger_test_feedback(&mut self) {}
+
+    /// Human-readable summary of what the device advertises, for bug reports.
+    fn describe(&self) -> String {
+        "No details available.".into()
+    }
 }
 
 pub struct DummyDevice;
@@ -40,6 +69,10 @@ impl Device for DummyDevice {
     }
 
     fn handle_events(&mut self) {}
+
+    fn describe(&self) -> String {
+        "Dummy device; no output.".into()
+    }
 }
 
 pub fn create_device(config: &config::Config) -> Result<Box<dyn Device>> {
@@ -47,7 +80,11 @@ pub fn create_device(config: &config::Config) -> Result<Box<dyn Device>> {
         config::Device::None => Box::new(DummyDevice),
         #[cfg(target_os = "linux")]
         config::Device::UInput => Box::new(UInputDevice::new(config)?),
+        #[cfg(target_os = "linux")]
+        config::Device::Keys => Box::new(KeysDevice::new(config)?),
+        #[cfg(target_os = "linux")]
+        config::Device::Mouse => Box::new(MouseDevice::new(config)?),
         #[cfg(target_os = "windows")]
-        config::Device::VigemBus => Box::new(VigemDevice::new()?),
+        config::Device::VigemBus => Box::new(VigemDevice::new(config)?),
     })
 }
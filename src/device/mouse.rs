@@ -0,0 +1,149 @@
+use std::{
+    fmt::Debug,
+    fs::{File, OpenOptions},
+    os::unix::fs::OpenOptionsExt,
+};
+
+use crate::{config::Config, device::Device};
+use anyhow::{Context, Result};
+use input_linux::{
+    EventKind, EventTime, InputEvent, InputId, Key, RelativeAxis, RelativeEvent, SynchronizeEvent,
+    SynchronizeKind, UInputHandle, sys::BUS_VIRTUAL,
+};
+use log::{debug, error, info};
+use nix::libc::O_NONBLOCK;
+
+const ZERO: EventTime = EventTime::new(0, 0);
+
+/// Virtual mouse which moves sideways as the wheel turns, for games steered by mouse.
+///
+/// The pointer is kept at a horizontal offset proportional to the wheel angle,
+/// by sending whatever relative motion is needed to get there from where it
+/// was last sent to.
+pub struct MouseDevice {
+    handle: UInputHandle<File>,
+    /// Degrees the wheel turns from centre to lock.
+    half_range: f32,
+    pixels_per_degree: f32,
+    /// Degrees either side of centre which don't move the mouse.
+    deadzone: f32,
+    /// Wheel angle in degrees.
+    angle: f32,
+    /// Offset in pixels the mouse has been moved to so far.
+    position: i32,
+}
+
+impl MouseDevice {
+    pub fn new(config: &Config) -> Result<Self> {
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .custom_flags(O_NONBLOCK)
+            .open("/dev/uinput")
+            .context("Could not open uinput file!")?;
+
+        let handle = UInputHandle::new(file);
+
+        handle.set_evbit(EventKind::Relative)?;
+        handle.set_relbit(RelativeAxis::X)?;
+        handle.set_relbit(RelativeAxis::Y)?;
+        // Never pressed, but needed for the device to be recognised as a mouse.
+        handle.set_evbit(EventKind::Key)?;
+        handle.set_keybit(Key::ButtonLeft)?;
+
+        let id = InputId {
+            bustype: BUS_VIRTUAL,
+            vendor: 0,
+            product: 0,
+            version: 1,
+        };
+
+        debug!(
+            "Creating virtual mouse:\n\tPixels per degree: {}\n\tDeadzone: {}°",
+            config.mouse_pixels_per_degree, config.mouse_deadzone
+        );
+
+        handle.create(&id, b"pen-steer mouse", 0, &[])?;
+
+        info!("Initialised!");
+
+        Ok(Self {
+            handle,
+            half_range: config.range * 0.5,
+            pixels_per_degree: config.mouse_pixels_per_degree,
+            deadzone: config.mouse_deadzone,
+            angle: 0.0,
+            position: 0,
+        })
+    }
+
+    /// Offset in pixels the mouse should be at for the current angle.
+    fn target_position(&self) -> i32 {
+        let past_deadzone = (self.angle.abs() - self.deadzone).max(0.0);
+        (self.angle.signum() * past_deadzone * self.pixels_per_degree).round() as i32
+    }
+}
+
+impl Device for MouseDevice {
+    fn get_feedback(&self) -> Option<f32> {
+        None
+    }
+
+    fn set_wheel(&mut self, angle: f32) {
+        self.angle = angle.clamp(-1.0, 1.0) * self.half_range;
+    }
+
+    fn set_horn(&mut self, _honking: bool) {}
+
+    fn apply(&mut self) -> Result<()> {
+        let target = self.target_position();
+        let delta = target - self.position;
+        if delta == 0 {
+            return Ok(());
+        }
+
+        let events = [
+            InputEvent::from(RelativeEvent::new(ZERO, RelativeAxis::X, delta)).into_raw(),
+            InputEvent::from(SynchronizeEvent::new(ZERO, SynchronizeKind::Report, 0)).into_raw(),
+        ];
+
+        self.handle
+            .write(&events)
+            .context("could not write events")?;
+
+        self.position = target;
+
+        Ok(())
+    }
+
+    fn handle_events(&mut self) {}
+
+    fn describe(&self) -> String {
+        format!(
+            "uinput virtual mouse\n\
+            \tAxis: relative X\n\
+            \tPixels per degree: {}, deadzone: {}°, half range: {}°",
+            self.pixels_per_degree, self.deadzone, self.half_range
+        )
+    }
+}
+
+impl Drop for MouseDevice {
+    fn drop(&mut self) {
+        // Move back to where the mouse started, so the game is left steering straight.
+        self.set_wheel(0.0);
+        if let Err(err) = self.apply() {
+            error!("Could not recentre mouse: {err}");
+        }
+
+        if let Err(err) = self.handle.dev_destroy() {
+            error!("Error occured destroying uinput device: {err}");
+        }
+    }
+}
+
+impl Debug for MouseDevice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("MouseDevice { /* fields */ }")
+    }
+}
@@ -2,9 +2,13 @@ use std::{
     fmt::Debug,
     fs::{File, OpenOptions},
     os::unix::fs::OpenOptionsExt,
+    time::{Duration, Instant},
 };
 
-use crate::{config::Config, device::Device};
+use crate::{
+    config::{AuxAxis, Config},
+    device::{Device, keys::parse_button_name},
+};
 use anyhow::{Context, Result, bail};
 use input_linux::{
     AbsoluteAxis, AbsoluteEvent, AbsoluteInfo, AbsoluteInfoSetup, EventKind, EventTime,
@@ -45,6 +49,41 @@ const NULL_EFFECT: ff_effect = ff_effect {
     u: [0u64; 4],
 };
 
+/// Buttons which are always advertised, even if unbound, as they can help
+/// applications recognise the virtual device.
+const BUTTONS: [Key; 6] = [
+    Key::ButtonThumbr,
+    Key::ButtonThumbl,
+    Key::ButtonNorth,
+    Key::ButtonEast,
+    Key::ButtonSouth,
+    Key::ButtonWest,
+];
+
+/// Only constant forces are used; the rest just help with detection.
+const FF_KINDS: [ForceFeedbackKind; 12] = [
+    ForceFeedbackKind::Constant,
+    ForceFeedbackKind::Autocenter,
+    ForceFeedbackKind::Periodic,
+    ForceFeedbackKind::Rumble,
+    ForceFeedbackKind::Damper,
+    ForceFeedbackKind::Inertia,
+    ForceFeedbackKind::Ramp,
+    ForceFeedbackKind::Sine,
+    ForceFeedbackKind::Square,
+    ForceFeedbackKind::Triangle,
+    ForceFeedbackKind::SawUp,
+    ForceFeedbackKind::SawDown,
+];
+
+/// Below this resolution, the axis steps are coarse enough to be worth smoothing.
+pub const LOW_RESOLUTION: u32 = 1024;
+/// Fraction of the remaining distance to the target covered each update when smoothing.
+const SMOOTHING_BLEND: f32 = 0.5;
+
+const TEST_FEEDBACK_LEVEL: f32 = 0.5;
+const TEST_FEEDBACK_DURATION: Duration = Duration::from_secs(1);
+
 #[derive(Default, Clone, Copy)]
 struct FFState {
     request_id: u32,
@@ -57,9 +96,26 @@ pub struct UInputDevice {
     resolution: f32,
     wheel_axis: i32,
     wheel_axis_prev: i32,
-    horn_key: bool,
-    horn_key_prev: bool,
+    /// Whether the axis eases towards `wheel_target` rather than jumping to it.
+    smoothing: bool,
+    wheel_target: f32,
+    wheel_smoothed: f32,
+    /// Added to the wheel angle, as a fraction of the axis, to shift its neutral.
+    center_offset: f32,
+    /// Extra axis which lean is sent on, if enabled.
+    aux_axis: Option<AbsoluteAxis>,
+    aux_value: i32,
+    aux_value_prev: i32,
+    /// Extra axis which the horn is sent on instead of its button, if enabled.
+    horn_axis: Option<AbsoluteAxis>,
+    horn_axis_prev: bool,
+    /// Bound buttons, in the order: horn, pen buttons 1 to 3, eraser, clutch.
+    bindings: [Option<Key>; 6],
+    pressed: [bool; 6],
+    pressed_prev: [bool; 6],
     ff: Option<FFState>,
+    test_feedback_start: Option<Instant>,
+    description: String,
 }
 
 impl UInputDevice {
@@ -85,21 +141,43 @@ impl UInputDevice {
 
         let handle = UInputHandle::new(file);
 
-        // Steering wheel horn button.
+        let [pen_button1, pen_button2, pen_button3] = &config.bindings.pen_buttons;
+        let bindings = [
+            &config.bindings.horn,
+            pen_button1,
+            pen_button2,
+            pen_button3,
+            &config.bindings.eraser,
+            &config.bindings.clutch,
+        ]
+        .map(|name| {
+            (!name.is_empty())
+                .then(|| parse_button_name(name))
+                .transpose()
+        });
+        let mut bindings = match bindings {
+            [Ok(a), Ok(b), Ok(c), Ok(d), Ok(e), Ok(f)] => [a, b, c, d, e, f],
+            _ => {
+                let err = bindings.into_iter().find_map(Result::err).unwrap();
+                return Err(err.context("Invalid button binding."));
+            }
+        };
+
+        // The horn goes on its axis instead, or nowhere when it is turned off.
+        if config.horn_axis_output || !config.horn_enabled {
+            bindings[0] = None;
+        }
+
         handle.set_evbit(EventKind::Key)?;
-        handle.set_keybit(Key::ButtonThumbr)?;
-        // Unused buttons; can help applications recognise the virtual device.
-        handle.set_keybit(Key::ButtonThumbl)?;
-        handle.set_keybit(Key::ButtonNorth)?;
-        handle.set_keybit(Key::ButtonEast)?;
-        handle.set_keybit(Key::ButtonSouth)?;
-        handle.set_keybit(Key::ButtonWest)?;
+        for button in BUTTONS.iter().chain(bindings.iter().flatten()) {
+            handle.set_keybit(*button)?;
+        }
 
         // Steering wheel absolute axis.
         handle.set_evbit(EventKind::Absolute)?;
         handle.set_absbit(AbsoluteAxis::X)?;
-        let abs = AbsoluteInfoSetup {
-            axis: AbsoluteAxis::X,
+        let abs_setup = |axis| AbsoluteInfoSetup {
+            axis,
             info: AbsoluteInfo {
                 value: 0,
                 minimum: -(config.device_resolution as i32),
@@ -109,22 +187,37 @@ impl UInputDevice {
                 resolution: config.device_resolution as i32,
             },
         };
+        let abs = abs_setup(AbsoluteAxis::X);
+
+        // Lean axis, if enabled.
+        let aux_axis = config
+            .lean_output
+            .then_some(absolute_axis(config.lean_axis));
+        let mut abs_setups = vec![abs];
+        if let Some(axis) = aux_axis {
+            handle.set_absbit(axis)?;
+            abs_setups.push(abs_setup(axis));
+        }
+
+        // Horn axis, if enabled. It only goes from released to fully pressed.
+        let horn_axis = (config.horn_enabled && config.horn_axis_output)
+            .then_some(absolute_axis(config.horn_axis));
+        if let Some(axis) = horn_axis {
+            if aux_axis == Some(axis) {
+                bail!("The horn and lean can't be sent on the same axis!");
+            }
+
+            handle.set_absbit(axis)?;
+            let mut setup = abs_setup(axis);
+            setup.info.minimum = 0;
+            abs_setups.push(setup);
+        }
 
         // Advertise force-feedback functionality.
         handle.set_evbit(EventKind::ForceFeedback)?;
-        handle.set_ffbit(ForceFeedbackKind::Constant)?;
-        // Ignored; just helps with detection.
-        handle.set_ffbit(ForceFeedbackKind::Autocenter)?;
-        handle.set_ffbit(ForceFeedbackKind::Periodic)?;
-        handle.set_ffbit(ForceFeedbackKind::Rumble)?;
-        handle.set_ffbit(ForceFeedbackKind::Damper)?;
-        handle.set_ffbit(ForceFeedbackKind::Inertia)?;
-        handle.set_ffbit(ForceFeedbackKind::Ramp)?;
-        handle.set_ffbit(ForceFeedbackKind::Sine)?;
-        handle.set_ffbit(ForceFeedbackKind::Square)?;
-        handle.set_ffbit(ForceFeedbackKind::Triangle)?;
-        handle.set_ffbit(ForceFeedbackKind::SawUp)?;
-        handle.set_ffbit(ForceFeedbackKind::SawDown)?;
+        for kind in FF_KINDS {
+            handle.set_ffbit(kind)?;
+        }
 
         let id = InputId {
             bustype: BUS_USB,
@@ -138,18 +231,49 @@ impl UInputDevice {
             config.device_name, config.device_vendor, config.device_product, config.device_version
         );
 
-        handle.create(&id, config.device_name.as_bytes(), 10, &[abs])?;
+        handle.create(&id, config.device_name.as_bytes(), 10, &abs_setups)?;
 
         info!("Initialised!");
 
+        let description = format!(
+            "uinput virtual controller\n\
+            \tName: {}\n\
+            \tID: {:04X}:{:04X} version {:04X} (USB)\n\
+            \tAxis: {:?} {} .. {}\n\
+            \tLean axis: {aux_axis:?}\n\
+            \tHorn axis: {horn_axis:?}\n\
+            \tButtons: {BUTTONS:?}\n\
+            \tBindings (horn, pen buttons, eraser, clutch): {bindings:?}\n\
+            \tForce feedback: {FF_KINDS:?}",
+            config.device_name,
+            config.device_vendor,
+            config.device_product,
+            config.device_version,
+            abs.axis,
+            abs.info.minimum,
+            abs.info.maximum,
+        );
+
         Ok(Self {
             handle,
             resolution: config.device_resolution as f32,
             wheel_axis: 0,
             wheel_axis_prev: 0,
-            horn_key: false,
-            horn_key_prev: false,
+            smoothing: config.device_smoothing && config.device_resolution < LOW_RESOLUTION,
+            wheel_target: 0.0,
+            wheel_smoothed: 0.0,
+            center_offset: config.output_center_offset,
+            aux_axis,
+            aux_value: 0,
+            aux_value_prev: 0,
+            horn_axis,
+            horn_axis_prev: false,
+            bindings,
+            pressed: [false; 6],
+            pressed_prev: [false; 6],
             ff: None,
+            test_feedback_start: None,
+            description,
         })
     }
 
@@ -216,26 +340,59 @@ impl UInputDevice {
 
 impl Device for UInputDevice {
     fn get_feedback(&self) -> Option<f32> {
+        if let Some(start) = self.test_feedback_start {
+            let elapsed = start.elapsed().as_secs_f32() / TEST_FEEDBACK_DURATION.as_secs_f32();
+            if elapsed < 1.0 {
+                return Some(TEST_FEEDBACK_LEVEL * (1.0 - elapsed));
+            }
+        }
+
         self.ff
             .and_then(|ff| ff.playing.then(|| ff.force as f32 / i16::MAX as f32))
     }
 
     fn set_wheel(&mut self, angle: f32) {
-        let value = (angle * self.resolution).round_ties_even();
-        self.wheel_axis = value as i32;
+        self.wheel_target = wheel_axis_value(angle, self.center_offset, self.resolution);
+
+        if !self.smoothing {
+            self.wheel_axis = self.wheel_target.round_ties_even() as i32;
+        }
     }
 
     fn set_horn(&mut self, honking: bool) {
-        self.horn_key = honking;
+        self.pressed[0] = honking;
+    }
+
+    fn set_pen_buttons(&mut self, buttons: u8) {
+        for bit in 0..3 {
+            self.pressed[1 + bit] = buttons & (1 << bit) != 0;
+        }
+    }
+
+    fn set_eraser(&mut self, touching: bool) {
+        self.pressed[4] = touching;
+    }
+
+    fn set_clutch(&mut self, engaged: bool) {
+        self.pressed[5] = engaged;
+    }
+
+    fn set_aux_axis(&mut self, value: f32) {
+        self.aux_value = (value.clamp(-1.0, 1.0) * self.resolution).round_ties_even() as i32;
     }
 
     fn apply(&mut self) -> Result<()> {
         const DELTA_THRESHOLD: i32 = 1;
 
-        // We only ever submit up to three events.
-        let mut events_buf = [NULL_EVENT; 3];
+        // Each axis, each of the bound buttons, and a sync report at most.
+        let mut events_buf = [NULL_EVENT; 10];
         let mut events_emitted = 0;
 
+        if self.smoothing {
+            self.wheel_smoothed += (self.wheel_target - self.wheel_smoothed) * SMOOTHING_BLEND;
+            self.wheel_axis = self.wheel_smoothed.round_ties_even() as i32;
+        }
+
         let delta_abs = (self.wheel_axis - self.wheel_axis_prev).abs();
         if delta_abs > DELTA_THRESHOLD {
             self.wheel_axis_prev = self.wheel_axis;
@@ -247,15 +404,47 @@ impl Device for UInputDevice {
             events_emitted += 1;
         }
 
-        if self.horn_key != self.horn_key_prev {
-            self.horn_key_prev = self.horn_key;
+        if let Some(axis) = self.aux_axis
+            && (self.aux_value - self.aux_value_prev).abs() > DELTA_THRESHOLD
+        {
+            self.aux_value_prev = self.aux_value;
+
+            events_buf[events_emitted] =
+                InputEvent::from(AbsoluteEvent::new(ZERO, axis, self.aux_value)).into_raw();
+
+            events_emitted += 1;
+        }
+
+        if let Some(axis) = self.horn_axis
+            && self.pressed[0] != self.horn_axis_prev
+        {
+            self.horn_axis_prev = self.pressed[0];
 
-            events_buf[events_emitted] = InputEvent::from(KeyEvent::new(
-                ZERO,
-                Key::ButtonThumbr,
-                KeyState::pressed(self.horn_key),
-            ))
-            .into_raw();
+            let value = if self.pressed[0] {
+                self.resolution as i32
+            } else {
+                0
+            };
+            events_buf[events_emitted] =
+                InputEvent::from(AbsoluteEvent::new(ZERO, axis, value)).into_raw();
+
+            events_emitted += 1;
+        }
+
+        for i in 0..self.bindings.len() {
+            if self.pressed[i] == self.pressed_prev[i] {
+                continue;
+            }
+
+            self.pressed_prev[i] = self.pressed[i];
+
+            let Some(key) = self.bindings[i] else {
+                continue;
+            };
+
+            events_buf[events_emitted] =
+                InputEvent::from(KeyEvent::new(ZERO, key, KeyState::pressed(self.pressed[i])))
+                    .into_raw();
 
             events_emitted += 1;
         }
@@ -311,18 +500,91 @@ impl Device for UInputDevice {
             }
         }
     }
+
+    fn trigger_test_feedback(&mut self) {
+        debug!("Playing test force-feedback.");
+        self.test_feedback_start = Some(Instant::now());
+    }
+
+    fn describe(&self) -> String {
+        self.description.clone()
+    }
 }
 
 impl Drop for UInputDevice {
     fn drop(&mut self) {
+        // Leave the wheel centred so games don't hold on to the last value.
+        self.smoothing = false;
+        self.set_wheel(0.0);
+        self.set_horn(false);
+        self.set_pen_buttons(0);
+        self.set_eraser(false);
+        self.set_clutch(false);
+        self.set_aux_axis(0.0);
+        if let Err(err) = self.apply() {
+            error!("Could not centre uinput device: {err}");
+        }
+
         if let Err(err) = self.handle.dev_destroy() {
             error!("Error occured destroying uinput device: {err}");
         }
     }
 }
 
+/// Value of the wheel axis for a normalised angle, shifted by the centre offset.
+/// Never exceeds the advertised axis range, even if the input overshoots.
+fn wheel_axis_value(angle: f32, center_offset: f32, resolution: f32) -> f32 {
+    ((angle + center_offset) * resolution).clamp(-resolution, resolution)
+}
+
+fn absolute_axis(axis: AuxAxis) -> AbsoluteAxis {
+    match axis {
+        AuxAxis::Y => AbsoluteAxis::Y,
+        AuxAxis::Z => AbsoluteAxis::Z,
+        AuxAxis::Rx => AbsoluteAxis::RX,
+        AuxAxis::Ry => AbsoluteAxis::RY,
+        AuxAxis::Rz => AbsoluteAxis::RZ,
+    }
+}
+
 impl Debug for UInputDevice {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.write_str("UInputDev { /* fields */ }")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RESOLUTION: f32 = 1000.0;
+
+    #[test]
+    fn overshooting_angles_stay_within_the_axis() {
+        for angle in [-5.0, -1.001, 1.001, 5.0, f32::MAX] {
+            let value = wheel_axis_value(angle, 0.0, RESOLUTION);
+            assert!(value.abs() <= RESOLUTION, "{angle} gave {value}");
+        }
+
+        assert_eq!(wheel_axis_value(1.5, 0.0, RESOLUTION), RESOLUTION);
+        assert_eq!(wheel_axis_value(-1.5, 0.0, RESOLUTION), -RESOLUTION);
+    }
+
+    #[test]
+    fn centre_offset_shifts_within_the_axis() {
+        assert_eq!(wheel_axis_value(0.0, 0.1, RESOLUTION), 100.0);
+        assert_eq!(wheel_axis_value(-0.5, -0.1, RESOLUTION), -600.0);
+
+        // Turned fully towards the offset, the output stops at the end of the axis.
+        assert_eq!(wheel_axis_value(1.0, 0.1, RESOLUTION), RESOLUTION);
+        assert_eq!(wheel_axis_value(-1.0, -0.25, RESOLUTION), -RESOLUTION);
+        assert_eq!(wheel_axis_value(-1.0, 0.1, RESOLUTION), -900.0);
+    }
+
+    #[test]
+    fn angles_within_range_scale_to_the_axis() {
+        assert_eq!(wheel_axis_value(0.0, 0.0, RESOLUTION), 0.0);
+        assert_eq!(wheel_axis_value(0.25, 0.0, RESOLUTION), 250.0);
+        assert_eq!(wheel_axis_value(-1.0, 0.0, RESOLUTION), -RESOLUTION);
+    }
+}
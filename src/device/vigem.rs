@@ -1,35 +1,101 @@
-use anyhow::{Context, Result};
-use log::{error, info};
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result, bail};
+use log::{error, info, warn};
 use vigem_client::{Client, TargetId, XButtons, XGamepad, Xbox360Wired};
 
-use crate::device::Device;
+use crate::{config::Config, device::Device};
+
+/// Time waited before the first attempt to reconnect, doubled after each failure.
+const RECONNECT_BACKOFF: Duration = Duration::from_millis(500);
+const MAX_RECONNECT_BACKOFF: Duration = Duration::from_secs(8);
 
 pub struct VigemDevice {
     target: Xbox360Wired<Client>,
+    scale: f32,
+    invert: bool,
+    /// Added to the stick position to shift its neutral.
+    center_offset: f32,
     last_angle: i16,
     last_horn_state: bool,
     dirty: bool,
+    /// Shortest time between updates sent to the driver.
+    min_interval: Duration,
+    last_send: Option<Instant>,
+    /// When to next try plugging the controller back in, if the bus went away.
+    reconnect_at: Option<Instant>,
+    reconnect_backoff: Duration,
+}
+
+/// Connect to the bus and plug in a fresh virtual controller.
+fn plug_in() -> Result<Xbox360Wired<Client>> {
+    let client = match Client::connect() {
+        Err(vigem_client::Error::BusNotFound) => bail!(
+            "The ViGEmBus driver does not appear to be installed.\n\
+            Install it from https://github.com/nefarius/ViGEmBus/releases, \
+            then reset the device."
+        ),
+        result => result.context("Could not connect to ViGEmBus.")?,
+    };
+    let mut target = Xbox360Wired::new(client, TargetId::XBOX360_WIRED);
+
+    target
+        .plugin()
+        .context("Failed to connect Vigem controller.")?;
+    target.wait_ready()?;
+
+    Ok(target)
 }
 
 impl VigemDevice {
-    pub fn new() -> Result<Self> {
+    pub fn new(config: &Config) -> Result<Self> {
         info!("Vigem device initialised!");
 
-        let client = Client::connect()?;
-        let mut target = Xbox360Wired::new(client, TargetId::XBOX360_WIRED);
-
-        target
-            .plugin()
-            .context("Failed to connect Vigem controller.")?;
-        target.wait_ready()?;
-
         Ok(Self {
-            target,
+            target: plug_in()?,
+            scale: config.vigem_scale,
+            invert: config.vigem_invert,
+            center_offset: config.output_center_offset,
             last_angle: 0,
             last_horn_state: false,
             dirty: true,
+            min_interval: if config.vigem_max_rate > 0 {
+                Duration::from_secs_f64(1.0 / config.vigem_max_rate as f64)
+            } else {
+                Duration::ZERO
+            },
+            last_send: None,
+            reconnect_at: None,
+            reconnect_backoff: RECONNECT_BACKOFF,
         })
     }
+
+    /// Plug a new controller in once the backoff has passed, after the bus went away.
+    fn try_reconnect(&mut self, now: Instant) {
+        if self.reconnect_at.is_none_or(|at| now < at) {
+            return;
+        }
+
+        match plug_in() {
+            Ok(target) => {
+                info!("Reconnected Vigem controller.");
+                self.target = target;
+                self.reconnect_at = None;
+                self.reconnect_backoff = RECONNECT_BACKOFF;
+                // Resend the held state so steering carries on where it left off.
+                self.dirty = true;
+                self.last_send = None;
+            }
+            Err(err) => {
+                warn!(
+                    "Could not reconnect Vigem controller, retrying in {:?}: {err}",
+                    self.reconnect_backoff
+                );
+                self.reconnect_at = Some(now + self.reconnect_backoff);
+                self.reconnect_backoff = (self.reconnect_backoff * 2).min(MAX_RECONNECT_BACKOFF);
+            }
+        }
+    }
 }
 
 impl Device for VigemDevice {
@@ -38,7 +104,8 @@ impl Device for VigemDevice {
     }
 
     fn set_wheel(&mut self, angle: f32) {
-        let clamped = angle.clamp(-1.0, 1.0);
+        let direction = if self.invert { -1.0 } else { 1.0 };
+        let clamped = (angle * self.scale * direction + self.center_offset).clamp(-1.0, 1.0);
         let value = (clamped * i16::MAX as f32) as i16;
 
         if self.last_angle != value {
@@ -55,17 +122,33 @@ impl Device for VigemDevice {
     }
 
     fn apply(&mut self) -> Result<()> {
+        let now = Instant::now();
+        if self.reconnect_at.is_some() {
+            self.try_reconnect(now);
+            if self.reconnect_at.is_some() {
+                return Ok(());
+            }
+        }
+
         if !self.dirty {
             return Ok(());
         }
 
+        // Hold changes back until the interval has passed; the latest state is sent then.
+        if self
+            .last_send
+            .is_some_and(|last| now.duration_since(last) < self.min_interval)
+        {
+            return Ok(());
+        }
+
         let buttons = if self.last_horn_state {
             XButtons::LTHUMB.into()
         } else {
             XButtons::default()
         };
 
-        self.target.update(&XGamepad {
+        let result = self.target.update(&XGamepad {
             buttons,
             left_trigger: 0,
             right_trigger: 0,
@@ -73,16 +156,50 @@ impl Device for VigemDevice {
             thumb_ly: 0,
             thumb_rx: 0,
             thumb_ry: 0,
-        })?;
+        });
+
+        // The bus may have restarted; keep the state and plug back in later.
+        if let Err(err) = result {
+            self.reconnect_at = Some(now + self.reconnect_backoff);
+            bail!("Lost connection to ViGEmBus, reconnecting: {err}");
+        }
+
+        self.dirty = false;
+        self.last_send = Some(now);
 
         Ok(())
     }
 
     fn handle_events(&mut self) {}
+
+    fn connected(&self) -> bool {
+        self.reconnect_at.is_none()
+    }
+
+    fn describe(&self) -> String {
+        format!(
+            "ViGEm virtual Xbox 360 controller\n\
+            \tAxis: left stick X, {} .. {}\n\
+            \tButtons: left thumb (horn)\n\
+            \tScale: {}, inverted: {}\n\
+            \tMinimum update interval: {:?}\n\
+            \tForce feedback: none",
+            i16::MIN,
+            i16::MAX,
+            self.scale,
+            self.invert,
+            self.min_interval
+        )
+    }
 }
 
 impl Drop for VigemDevice {
     fn drop(&mut self) {
+        // Leave the stick centred so games don't hold on to the last value.
+        if let Err(err) = self.target.update(&XGamepad::default()) {
+            error!("Could not centre Vigem controller: {err}");
+        }
+
         if let Err(err) = self.target.unplug() {
             error!("Could not unplug Vigem controller: {err}");
         }
@@ -1,16 +1,69 @@
 use std::{
+    collections::VecDeque,
+    f32::consts::FRAC_PI_2,
     path::PathBuf,
-    sync::{Arc, Mutex, atomic::{AtomicBool, Ordering}},
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, Receiver, TryRecvError},
+    },
+    time::{Duration, Instant},
 };
 
 use crate::{
-    config::{self, Config}, mapping::MapOrientation, math, pen::Pen, save::{compile_parse_errors, load_file, save_file}, save_path::{save_dir, save_path}, state::State, wheel::Wheel
+    config::{self, Config}, input_dump::InputDump, logging, mapping::{ClampShape, Handedness, LANDMARKS, MapMode, MapOrientation}, math, pen::{Pen, RawPen, SECOND_CONTACT_BIT}, physics_capture::{CAPTURE_DURATION, PhysicsCapture}, save::{compile_parse_errors, load_file, load_mapping_file, save_file, save_mapping_file}, save_path::{save_dir, save_path}, state::State, wheel::Wheel
 };
 use anyhow::anyhow;
 use eframe::egui::{
     self, Color32, Context, CornerRadius, Frame, Id, Layout, OpenUrl, Pos2, Rect, RichText, Sense, Stroke, Ui, Vec2, ViewportBuilder
 };
-use log::{debug, error};
+use log::{LevelFilter, debug, error, info};
+
+const WINDOW_TITLE: &str = "Pen Steer";
+
+#[derive(Clone, Copy)]
+enum ResetTarget {
+    Source,
+    Device,
+}
+
+#[derive(Clone, Copy)]
+enum WheelImagePart {
+    Base,
+    Inner,
+}
+
+/// What the path picked in a file dialog is for.
+#[derive(Clone, Copy)]
+enum DialogPurpose {
+    SaveConfig,
+    LoadConfig,
+    ExportMapping,
+    ImportMapping,
+    WheelImage(WheelImagePart),
+    DumpInput,
+    CapturePhysics,
+}
+
+impl DialogPurpose {
+    fn description(self) -> &'static str {
+        match self {
+            DialogPurpose::SaveConfig => "config file save path",
+            DialogPurpose::LoadConfig => "config file path",
+            DialogPurpose::ExportMapping => "mapping file save path",
+            DialogPurpose::ImportMapping => "mapping file path",
+            DialogPurpose::WheelImage(_) => "wheel image path",
+            DialogPurpose::DumpInput => "input dump save path",
+            DialogPurpose::CapturePhysics => "physics capture save path",
+        }
+    }
+}
+
+/// A file dialog open on another thread.
+struct PendingDialog {
+    purpose: DialogPurpose,
+    receiver: Receiver<native_dialog::Result<Option<PathBuf>>>,
+}
 
 #[derive(Clone, Copy)]
 enum SaveAction {
@@ -22,14 +75,64 @@ enum SaveAction {
 pub struct GuiApp {
     state: Arc<Mutex<State>>,
     quit_flag: Arc<AtomicBool>,
+    /// Hide the window instead of closing it, so it can be shown again later.
+    hide_on_close: bool,
+    /// Whether the window has been hidden.
+    hidden: bool,
+    /// File written by Save, taking `--config` into account.
     save_path: PathBuf,
-    evdev_available_devices: Option<Vec<String>>,
+    #[cfg(target_os = "linux")]
+    evdev_available_devices: Option<Vec<crate::source::evdev::EvdevDeviceInfo>>,
     dirty_source_config: bool,
     dirty_device_config: bool,
+    /// Reset waiting for the user to confirm replacing a working source or device.
+    pending_reset: Option<ResetTarget>,
+    /// Game preset waiting for the user to confirm overwriting their own tuning.
+    pending_preset: Option<config::GamePreset>,
     save_action: SaveAction,
     should_load: bool,
+    should_export_mapping: bool,
+    should_import_mapping: bool,
+    should_dump_input: bool,
+    should_capture_physics: bool,
+    pick_wheel_image: Option<WheelImagePart>,
+    should_copy_report: bool,
+    pending_dialog: Option<PendingDialog>,
     show_wheel: bool,
     show_about: bool,
+    show_setup: bool,
+    smooth_pen_dot: bool,
+    smoothed_pen_pos: Option<Vec2>,
+    smooth_steer_bar: bool,
+    /// Angle the steer bar was last drawn at, when smoothing it. Display only.
+    smoothed_bar_angle: Option<f32>,
+    average_ff_bar: bool,
+    rounded_bars: bool,
+    show_log: bool,
+    show_tick_timing: bool,
+    show_mapping_debug: bool,
+    /// Show the wheel angle and update rate in the window title.
+    status_in_title: bool,
+    /// Title last sent to the window, and when.
+    window_title: (String, Instant),
+    /// Also draw where the pen is before mapping.
+    show_raw_pen: bool,
+    /// Draw the feedback torque as an arc around the rim of the wheel.
+    show_feedback_overlay: bool,
+    /// Draw the wheel and steer bar turning the other way. Display only.
+    invert_wheel_display: bool,
+    /// Show angles as a percentage of the way to lock instead of in degrees.
+    angle_as_lock_percent: bool,
+    /// Show torques as a percentage of the max feedback torque instead of in Nm.
+    torque_as_percent: bool,
+    /// Show a free entry for the update frequency instead of only the presets.
+    custom_update_frequency: bool,
+    /// Recent feedback torques shown by the averaged FF bar.
+    ff_history: VecDeque<f32>,
+    /// Quarter turns applied to the wheel image's neutral position.
+    wheel_image_quarter_turns: u8,
+    /// Ignore the mouse on the wheel until it is released, after Escape was pressed.
+    pen_override_suppressed: bool,
     device_vendor_edit_buf: String,
     device_product_edit_buf: String,
     device_version_edit_buf: String,
@@ -45,11 +148,30 @@ impl eframe::App for GuiApp {
         let state_arc = self.state.clone();
         let mut state = state_arc.lock().unwrap();
 
+        if self.hide_on_close {
+            self.update_visibility(ctx, &mut state);
+        }
+
+        state.gui_unfocused = !self.hidden && !ctx.input(|i| i.focused);
+
         if let Some(err) = state.last_error.take() {
             show_error(frame, err);
         }
 
+        if let Some(key) = egui::Key::from_name(&state.config.panic_key)
+            && ctx.input(|i| i.key_pressed(key))
+        {
+            state.pause();
+        }
+
         self.draw_ui(ctx, &mut state);
+        self.update_title(ctx, &state);
+
+        if self.should_copy_report {
+            self.should_copy_report = false;
+            ctx.copy_text(device_report(&state));
+        }
+
         drop(state);
 
         if self.show_wheel {
@@ -58,25 +180,68 @@ impl eframe::App for GuiApp {
 
         self.save();
         self.load();
+        self.export_mapping();
+        self.import_mapping();
+        self.pick_wheel_image();
+        self.dump_input();
+        self.capture_physics();
+        self.finish_dialog();
+
+        if self.pending_dialog.is_some() {
+            ctx.request_repaint_after(Duration::from_millis(100));
+        }
     }
 }
 
 impl GuiApp {
-    pub fn new(state: Arc<Mutex<State>>, quit_flag: Arc<AtomicBool>) -> Self {
+    pub fn new(state: Arc<Mutex<State>>, quit_flag: Arc<AtomicBool>, hide_on_close: bool) -> Self {
         let save_path = save_path();
-        let show_about = !save_path.exists();
+        let show_setup = !save_path.exists();
 
         Self {
             state,
             quit_flag,
+            hide_on_close,
+            hidden: false,
             save_path,
+            #[cfg(target_os = "linux")]
             evdev_available_devices: None,
             dirty_source_config: false,
             dirty_device_config: false,
+            pending_reset: None,
+            pending_preset: None,
             save_action: SaveAction::None,
             should_load: false,
+            should_export_mapping: false,
+            should_import_mapping: false,
+            should_dump_input: false,
+            should_capture_physics: false,
+            pick_wheel_image: None,
+            should_copy_report: false,
+            pending_dialog: None,
             show_wheel: true,
-            show_about,
+            show_about: false,
+            show_setup,
+            smooth_pen_dot: false,
+            smooth_steer_bar: false,
+            smoothed_bar_angle: None,
+            smoothed_pen_pos: None,
+            average_ff_bar: false,
+            rounded_bars: false,
+            show_log: false,
+            show_tick_timing: false,
+            show_mapping_debug: false,
+            status_in_title: true,
+            window_title: (WINDOW_TITLE.into(), Instant::now()),
+            show_raw_pen: false,
+            show_feedback_overlay: false,
+            invert_wheel_display: false,
+            angle_as_lock_percent: false,
+            torque_as_percent: false,
+            custom_update_frequency: false,
+            ff_history: VecDeque::new(),
+            wheel_image_quarter_turns: 0,
+            pen_override_suppressed: false,
             device_vendor_edit_buf: String::new(),
             device_product_edit_buf: String::new(),
             device_version_edit_buf: String::new(),
@@ -88,27 +253,19 @@ impl GuiApp {
         let action = self.save_action;
         self.save_action = SaveAction::None;
 
-        let path = match action {
-            SaveAction::None => {
-                return;
-            }
-            SaveAction::ToCurrentPath => self.save_path.clone(),
-            SaveAction::ToCustomPath => {
-                match native_dialog::FileDialogBuilder::default()
+        match action {
+            SaveAction::None => {}
+            SaveAction::ToCurrentPath => self.save_to(self.save_path.clone()),
+            SaveAction::ToCustomPath => self.open_dialog(DialogPurpose::SaveConfig, || {
+                native_dialog::FileDialogBuilder::default()
                     .set_location(&save_dir())
                     .save_single_file()
                     .show()
-                {
-                    Ok(Some(path)) => path,
-                    Ok(None) => return,
-                    Err(err) => {
-                        error!("Could not pick config file save path: {err}");
-                        return;
-                    }
-                }
-            }
-        };
+            }),
+        }
+    }
 
+    fn save_to(&mut self, path: PathBuf) {
         let config = self.state.lock().unwrap().config.clone();
         debug!("Saving configuration to {}", path.display());
         if let Err(err) = save_file(&config, &path) {
@@ -124,19 +281,15 @@ impl GuiApp {
 
         self.should_load = false;
 
-        let path = match native_dialog::FileDialogBuilder::default()
-            .set_location(&save_dir())
-            .open_single_file()
-            .show()
-        {
-            Ok(Some(path)) => path,
-            Ok(None) => return,
-            Err(err) => {
-                error!("Could not pick config file path: {err}");
-                return;
-            }
-        };
+        self.open_dialog(DialogPurpose::LoadConfig, || {
+            native_dialog::FileDialogBuilder::default()
+                .set_location(&save_dir())
+                .open_single_file()
+                .show()
+        });
+    }
 
+    fn load_from(&mut self, path: PathBuf) {
         debug!("Loading configuration at {}", path.display());
         let mut new_config = Config::default();
         let parse_errors = match load_file(&mut new_config, &path) {
@@ -162,6 +315,188 @@ impl GuiApp {
         self.device_product_edit_buf.clear();
         self.device_version_edit_buf.clear();
     }
+
+    fn pick_wheel_image(&mut self) {
+        let Some(part) = self.pick_wheel_image.take() else {
+            return;
+        };
+
+        self.open_dialog(DialogPurpose::WheelImage(part), || {
+            native_dialog::FileDialogBuilder::default()
+                .add_filter("Image", ["svg", "png"])
+                .open_single_file()
+                .show()
+        });
+    }
+
+    fn set_wheel_image(&mut self, part: WheelImagePart, path: PathBuf) {
+        let path = Some(path.to_string_lossy().into_owned());
+        let config = &mut self.state.lock().unwrap().config;
+        match part {
+            WheelImagePart::Base => config.wheel_base_image = path,
+            WheelImagePart::Inner => config.wheel_inner_image = path,
+        }
+    }
+
+    fn export_mapping(&mut self) {
+        if !self.should_export_mapping {
+            return;
+        }
+
+        self.should_export_mapping = false;
+
+        self.open_dialog(DialogPurpose::ExportMapping, || {
+            native_dialog::FileDialogBuilder::default()
+                .set_location(&save_dir())
+                .save_single_file()
+                .show()
+        });
+    }
+
+    fn export_mapping_to(&mut self, path: PathBuf) {
+        let mapping = self.state.lock().unwrap().config.mapping.clone();
+        debug!("Exporting mapping to {}", path.display());
+        if let Err(err) = save_mapping_file(&mapping, &path) {
+            self.state.lock().unwrap().last_error =
+                Some(err.context("Could not export mapping file."));
+        }
+    }
+
+    fn import_mapping(&mut self) {
+        if !self.should_import_mapping {
+            return;
+        }
+
+        self.should_import_mapping = false;
+
+        self.open_dialog(DialogPurpose::ImportMapping, || {
+            native_dialog::FileDialogBuilder::default()
+                .set_location(&save_dir())
+                .open_single_file()
+                .show()
+        });
+    }
+
+    fn import_mapping_from(&mut self, path: PathBuf) {
+        debug!("Importing mapping at {}", path.display());
+        let mut mapping = self.state.lock().unwrap().config.mapping.clone();
+        let parse_errors = match load_mapping_file(&mut mapping, &path) {
+            Ok(parse_errors) => parse_errors,
+            Err(load_err) => {
+                self.state.lock().unwrap().last_error =
+                    Some(load_err.context("Could not import mapping file."));
+                return;
+            }
+        };
+
+        let mut state = self.state.lock().unwrap();
+        if !parse_errors.is_empty() {
+            state.last_error = Some(anyhow!(compile_parse_errors(parse_errors)));
+        }
+
+        state.config.mapping = mapping;
+    }
+
+    fn dump_input(&mut self) {
+        if !self.should_dump_input {
+            return;
+        }
+
+        self.should_dump_input = false;
+
+        self.open_dialog(DialogPurpose::DumpInput, || {
+            native_dialog::FileDialogBuilder::default()
+                .set_location(&save_dir())
+                .add_filter("CSV", ["csv"])
+                .save_single_file()
+                .show()
+        });
+    }
+
+    fn capture_physics(&mut self) {
+        if !self.should_capture_physics {
+            return;
+        }
+
+        self.should_capture_physics = false;
+
+        self.open_dialog(DialogPurpose::CapturePhysics, || {
+            native_dialog::FileDialogBuilder::default()
+                .set_location(&save_dir())
+                .add_filter("CSV", ["csv"])
+                .save_single_file()
+                .show()
+        });
+    }
+
+    fn start_input_dump(&mut self, path: PathBuf) {
+        let mut state = self.state.lock().unwrap();
+        match InputDump::create(&path) {
+            Ok(dump) => {
+                info!("Dumping input samples to: {}", path.display());
+                state.input_dump = Some(dump);
+            }
+            Err(err) => state.last_error = Some(err),
+        }
+    }
+
+    /// Show a file dialog on its own thread, so that the window keeps drawing
+    /// while it is open. Its result is handled by `finish_dialog`.
+    fn open_dialog(
+        &mut self,
+        purpose: DialogPurpose,
+        show: impl FnOnce() -> native_dialog::Result<Option<PathBuf>> + Send + 'static,
+    ) {
+        if self.pending_dialog.is_some() {
+            debug!("A file dialog is already open.");
+            return;
+        }
+
+        let (sender, receiver) = mpsc::channel();
+        std::thread::spawn(move || {
+            let _ = sender.send(show());
+        });
+
+        self.pending_dialog = Some(PendingDialog { purpose, receiver });
+    }
+
+    fn finish_dialog(&mut self) {
+        let Some(dialog) = &self.pending_dialog else {
+            return;
+        };
+
+        let result = match dialog.receiver.try_recv() {
+            Ok(result) => result,
+            Err(TryRecvError::Empty) => return,
+            Err(TryRecvError::Disconnected) => Ok(None),
+        };
+        let purpose = dialog.purpose;
+        self.pending_dialog = None;
+
+        let path = match result {
+            Ok(Some(path)) => path,
+            Ok(None) => return,
+            Err(err) => {
+                error!("Could not pick {}: {err}", purpose.description());
+                return;
+            }
+        };
+
+        match purpose {
+            DialogPurpose::SaveConfig => self.save_to(path),
+            DialogPurpose::LoadConfig => self.load_from(path),
+            DialogPurpose::ExportMapping => self.export_mapping_to(path),
+            DialogPurpose::ImportMapping => self.import_mapping_from(path),
+            DialogPurpose::WheelImage(part) => self.set_wheel_image(part, path),
+            DialogPurpose::DumpInput => self.start_input_dump(path),
+            DialogPurpose::CapturePhysics => {
+                let mut state = self.state.lock().unwrap();
+                let capture = PhysicsCapture::new(path, state.config.update_frequency);
+                info!("Capturing physics to: {}", capture.path().display());
+                state.physics_capture = Some(capture);
+            }
+        }
+    }
 }
 
 fn show_error(frame: &eframe::Frame, err: anyhow::Error) {
@@ -180,7 +515,11 @@ impl GuiApp {
     fn draw_menu(&mut self, ui: &mut Ui) {
         ui.horizontal(|ui| {
             ui.menu_button("File", |ui| {
-                if ui.button("Save").clicked() {
+                if ui
+                    .button("Save")
+                    .on_hover_text(format!("Save to {}", self.save_path.display()))
+                    .clicked()
+                {
                     self.save_action = SaveAction::ToCurrentPath;
                 }
 
@@ -190,14 +529,112 @@ impl GuiApp {
 
                 self.should_load |= ui.button("Load...").clicked();
 
+                ui.separator();
+                self.should_export_mapping |= ui.button("Export Mapping...").clicked();
+                self.should_import_mapping |= ui.button("Import Mapping...").clicked();
+
+                ui.separator();
+                let mut state = self.state.lock().unwrap();
+                if let Some(dump) = &state.input_dump {
+                    if ui
+                        .button("Stop Input Dump")
+                        .on_hover_text(dump.path().display().to_string())
+                        .clicked()
+                    {
+                        info!("Stopped dumping input samples.");
+                        state.input_dump = None;
+                    }
+                } else {
+                    self.should_dump_input |= ui
+                        .button("Dump Input...")
+                        .on_hover_text("Log every sample from the source to a CSV file.")
+                        .clicked();
+                }
+
+                let capturing = state.physics_capture.is_some();
+                drop(state);
+                self.should_capture_physics |= ui
+                    .add_enabled(
+                        !capturing,
+                        egui::Button::new(format!(
+                            "Capture Physics ({}s)...",
+                            CAPTURE_DURATION.as_secs()
+                        )),
+                    )
+                    .on_hover_text(
+                        "Record the wheel's state on every update for a few seconds, \
+                        and save it as a CSV file to attach to a bug report.",
+                    )
+                    .clicked();
+
                 ui.separator();
                 if ui.button("Quit").clicked() {
                     ui.ctx().send_viewport_cmd(egui::ViewportCommand::Close);
                 }
             });
 
-            ui.menu_button("Help", |ui| if ui.button("About").clicked() {
-                self.show_about = true;
+            ui.menu_button("View", |ui| {
+                ui.checkbox(&mut self.show_log, "Log");
+                ui.checkbox(&mut self.show_tick_timing, "Tick timing")
+                    .on_hover_text("Histogram of how regularly the controller wakes up.");
+                ui.checkbox(&mut self.status_in_title, "Status in title")
+                    .on_hover_text("Show the wheel angle and update rate in the window title.");
+                ui.checkbox(&mut self.show_mapping_debug, "Mapping debug")
+                    .on_hover_text("Where the mapping sends the corners and centre of the tablet.");
+                ui.checkbox(&mut self.smooth_pen_dot, "Smooth pen dot");
+                ui.checkbox(&mut self.smooth_steer_bar, "Smooth steer bar");
+                ui.checkbox(&mut self.average_ff_bar, "Average FF bar");
+                ui.checkbox(&mut self.rounded_bars, "Rounded bars");
+                ui.checkbox(&mut self.show_raw_pen, "Show raw pen")
+                    .on_hover_text("Also mark where the pen is before mapping.");
+                ui.checkbox(&mut self.show_feedback_overlay, "Feedback overlay")
+                    .on_hover_text(
+                        "Show which way, and how hard, force feedback is pushing the wheel.",
+                    );
+                ui.checkbox(&mut self.invert_wheel_display, "Invert wheel display")
+                    .on_hover_text(
+                        "Draw the wheel and steer bar turning the other way. \
+                        Does not change the output.",
+                    );
+
+                ui.separator();
+                ui.label("Angles in:");
+                ui.radio_value(&mut self.angle_as_lock_percent, false, "Degrees");
+                ui.radio_value(&mut self.angle_as_lock_percent, true, "% of lock");
+                ui.label("Torques in:");
+                ui.radio_value(&mut self.torque_as_percent, false, "Nm");
+                ui.radio_value(&mut self.torque_as_percent, true, "% of max feedback");
+
+                ui.separator();
+                ui.label("Wheel image neutral:");
+                ui.radio_value(&mut self.wheel_image_quarter_turns, 0, "Up");
+                ui.radio_value(&mut self.wheel_image_quarter_turns, 1, "Right");
+                ui.radio_value(&mut self.wheel_image_quarter_turns, 2, "Down");
+                ui.radio_value(&mut self.wheel_image_quarter_turns, 3, "Left");
+
+                ui.separator();
+                if ui.button("Wheel image...").clicked() {
+                    self.pick_wheel_image = Some(WheelImagePart::Base);
+                }
+                if ui.button("Wheel centre image...").clicked() {
+                    self.pick_wheel_image = Some(WheelImagePart::Inner);
+                }
+                if ui.button("Use default wheel images").clicked() {
+                    let config = &mut self.state.lock().unwrap().config;
+                    config.wheel_base_image = None;
+                    config.wheel_inner_image = None;
+                }
+            });
+
+            ui.menu_button("Help", |ui| {
+                self.should_copy_report |= ui
+                    .button("Copy Device Report")
+                    .on_hover_text("Copy details of the open source and device, for bug reports.")
+                    .clicked();
+
+                if ui.button("About").clicked() {
+                    self.show_about = true;
+                }
             });
 
             ui.with_layout(Layout::right_to_left(egui::Align::Max), |ui| {
@@ -212,13 +649,36 @@ impl GuiApp {
     fn draw_ui(&mut self, ctx: &Context, state: &mut State) {
         egui::TopBottomPanel::top("menu").show(ctx, |ui| self.draw_menu(ui));
 
+        if state.paused {
+            egui::TopBottomPanel::top("paused_banner")
+                .frame(Frame::new().fill(Color32::DARK_RED).inner_margin(8.0))
+                .show(ctx, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label(
+                            RichText::new("PAUSED")
+                                .heading()
+                                .strong()
+                                .color(Color32::WHITE),
+                        );
+                        ui.label(
+                            RichText::new("Output is neutral and the wheel is not simulated.")
+                                .color(Color32::WHITE),
+                        );
+
+                        if ui.button("Resume").clicked() {
+                            state.resume();
+                        }
+                    });
+                });
+        }
+
         egui::SidePanel::left("controls")
             .resizable(false)
             .show(ctx, |ui| {
                 ui.set_width(350.0);
                 ui.style_mut().spacing.slider_width = 200.0;
 
-                const FOOTER_HEIGHT: f32 = 70.0;
+                const FOOTER_HEIGHT: f32 = 90.0;
                 egui::TopBottomPanel::bottom("controls_footer")
                     .exact_height(FOOTER_HEIGHT)
                     .show_inside(ui, |ui| {
@@ -245,7 +705,17 @@ impl GuiApp {
                 });
             });
 
+        self.draw_setup(ctx, state);
+        self.draw_log(ctx);
+        self.draw_reset_confirmation(ctx, state);
+        self.draw_preset_confirmation(ctx, &mut state.config);
+        self.draw_tick_timing(ctx, state);
+        self.draw_mapping_debug(ctx, state);
+
         if !self.show_wheel {
+            // the wheel can't be dragged while hidden
+            state.pen_override = None;
+            state.wheel.manual_target = None;
             self.draw_steering_wheel_placeholder(ctx);
             return;
         }
@@ -253,125 +723,657 @@ impl GuiApp {
         egui::TopBottomPanel::bottom("steer_bar")
             .exact_height(32.0)
             .show(ctx, |ui| {
-                if let Some(new_angle) = draw_steer_bar(state.wheel.angle, &state.config, ui) {
-                    state.wheel.angle = new_angle;
+                let sign = self.wheel_display_sign();
+                let angle = self.smooth_bar_angle(ctx, sign * state.wheel.angle);
+                if let Some(new_angle) = draw_steer_bar(angle, &state.config, self.rounded_bars, ui)
+                {
+                    state.wheel.angle = sign * new_angle;
                 }
             });
 
-        if let Some(device) = &state.device {
-            if device.get_feedback().is_some() {
-                egui::TopBottomPanel::bottom("ff_bar")
-                    .exact_height(16.0)
-                    .show(ctx, |ui| {
-                        draw_ff_bar(state.wheel.feedback_torque, state.config.max_torque, ui);
-                    });
-            }
+        if let Some(device) = &state.device
+            && device.get_feedback().is_some()
+        {
+            egui::TopBottomPanel::bottom("ff_bar")
+                .exact_height(16.0)
+                .show(ctx, |ui| {
+                    let torque = state.wheel.feedback_torque;
+                    if self.average_ff_bar {
+                        let (average, band) = self.record_ff_torque(torque);
+                        draw_ff_bar(
+                            average,
+                            Some(band),
+                            state.config.max_torque,
+                            self.rounded_bars,
+                            ui,
+                        );
+                    } else {
+                        self.ff_history.clear();
+                        draw_ff_bar(torque, None, state.config.max_torque, self.rounded_bars, ui);
+                    }
+                });
         }
 
         egui::CentralPanel::default().show(ctx, |ui| {
-            let pen = state.pen_override.as_ref().or(state.pen.as_ref());
-            state.pen_override = draw_steering_wheel(
+            let pen = state.pen_override.as_ref().or(state.pen.as_ref()).cloned();
+            let pen = self.smooth_pen(ctx, pen);
+            let sign = self.wheel_display_sign();
+            let image_offset = self.wheel_image_quarter_turns as f32 * FRAC_PI_2;
+            let raw_pen = state.raw_pen.as_ref().filter(|_| self.show_raw_pen);
+            let pen_override = draw_steering_wheel(
                 &state.config,
                 &state.wheel,
                 self.base_radius_selection,
-                pen.cloned(),
+                sign * state.wheel.angle + image_offset,
+                pen,
+                raw_pen,
                 ui,
             );
+            if self.show_feedback_overlay {
+                draw_feedback_overlay(&state.config, &state.wheel, sign, ui);
+            }
+
+            if state.pen_override.is_some() && ui.input(|i| i.key_pressed(egui::Key::Escape)) {
+                self.pen_override_suppressed = true;
+            }
+
+            if !ui.input(|i| i.pointer.primary_down()) {
+                self.pen_override_suppressed = false;
+            }
+
+            state.pen_override = pen_override.filter(|_| !self.pen_override_suppressed);
+
+            if state.pen_override.is_some() {
+                ui.colored_label(
+                    Color32::YELLOW,
+                    "Manual override active. (Release or press Esc to stop)",
+                );
+            }
         });
 
         draw_about(ctx, &mut self.show_about);
     }
 
-    fn draw_controls_footer(&mut self, ui: &mut Ui, state: &mut State) {
-        ui.add_space(10.0);
-        let width = ui.clip_rect().width() * 0.46;
+    /// Hide or show the window as asked through `State::hide_gui`, and hide it
+    /// instead of closing it.
+    fn update_visibility(&mut self, ctx: &Context, state: &mut State) {
+        const POLL_INTERVAL: Duration = Duration::from_millis(100);
 
-        let source_btn = egui::Button::new(RichText::new("Reset Source").color(
-            if self.dirty_source_config {
-                Color32::ORANGE
-            } else {
-                Color32::WHITE
-            },
-        ))
-        .min_size(Vec2::new(width, 0.0));
+        if ctx.input(|i| i.viewport().close_requested()) && !self.quit_flag.load(Ordering::Acquire)
+        {
+            ctx.send_viewport_cmd(egui::ViewportCommand::CancelClose);
+            state.hide_gui = true;
+        }
 
-        let device_btn = egui::Button::new(RichText::new("Reset Device").color(
-            if self.dirty_device_config {
-                Color32::ORANGE
+        if state.hide_gui != self.hidden {
+            self.hidden = state.hide_gui;
+            ctx.send_viewport_cmd(egui::ViewportCommand::Visible(!self.hidden));
+            if self.hidden {
+                info!("GUI hidden; still running headless.");
             } else {
-                Color32::WHITE
-            },
-        ))
-        .min_size(Vec2::new(width, 0.0));
-
-        ui.horizontal(|ui| {
-            if ui.add(source_btn).clicked() {
-                state.reset_source = true;
-                self.dirty_source_config = false;
+                ctx.send_viewport_cmd(egui::ViewportCommand::Focus);
+                info!("Showing the GUI.");
             }
+        }
 
-            if ui.add(device_btn).clicked() {
-                state.reset_device = true;
-                self.dirty_device_config = false;
-            }
-        });
+        // Nothing else wakes a hidden window, so keep checking for requests to show it.
+        ctx.request_repaint_after(POLL_INTERVAL);
+    }
 
-        ui.add_space(5.0);
+    /// Keep the window title showing live status, at a throttled rate.
+    fn update_title(&mut self, ctx: &Context, state: &State) {
+        const INTERVAL: Duration = Duration::from_millis(250);
 
-        ui.horizontal(|ui| {
-            if ui
-                .add(egui::Button::new("Save").min_size(Vec2::new(width, 0.0)))
-                .clicked()
-            {
-                self.save_action = SaveAction::ToCurrentPath;
+        let title = if self.status_in_title {
+            let angle = format_angle(
+                state.wheel.angle,
+                state.config.half_range_rad(),
+                self.angle_as_lock_percent,
+                0,
+            );
+            let mut title = format!("{WINDOW_TITLE} — {angle}");
+            if let Some(rate) = state.measured_update_rate() {
+                title += &format!(" — {rate:.0} Hz");
             }
+            if state.paused {
+                title += " — paused";
+            }
+            title
+        } else {
+            WINDOW_TITLE.to_owned()
+        };
 
-            self.should_load |= ui
-                .add(egui::Button::new("Load...").min_size(Vec2::new(width, 0.0)))
-                .clicked()
-        });
-    }
+        let (shown, updated) = &self.window_title;
+        if title == *shown || (self.status_in_title && updated.elapsed() < INTERVAL) {
+            return;
+        }
 
-    fn draw_controls(&mut self, state: &mut State, ui: &mut Ui) {
-        let config = &mut state.config;
+        ctx.send_viewport_cmd(egui::ViewportCommand::Title(title.clone()));
+        self.window_title = (title, Instant::now());
+    }
 
-        egui::ComboBox::new("update_freq", "Update Frequency")
-            .selected_text(format!("{} Hz", config.update_frequency))
-            .show_ui(ui, |ui| {
-                ui.selectable_value(&mut config.update_frequency, 5, "5 Hz");
-                ui.selectable_value(&mut config.update_frequency, 30, "30 Hz");
-                ui.selectable_value(&mut config.update_frequency, 50, "50 Hz");
-                ui.selectable_value(&mut config.update_frequency, 60, "60 Hz");
-                ui.selectable_value(&mut config.update_frequency, 100, "100 Hz");
-                ui.selectable_value(&mut config.update_frequency, 125, "125 Hz");
-                ui.selectable_value(&mut config.update_frequency, 500, "500 Hz");
-                ui.selectable_value(&mut config.update_frequency, 1000, "1000 Hz");
-            });
+    fn draw_log(&mut self, ctx: &Context) {
+        const LEVELS: [LevelFilter; 6] = [
+            LevelFilter::Off,
+            LevelFilter::Error,
+            LevelFilter::Warn,
+            LevelFilter::Info,
+            LevelFilter::Debug,
+            LevelFilter::Trace,
+        ];
+
+        egui::Window::new("Log")
+            .open(&mut self.show_log)
+            .default_size([600.0, 300.0])
+            .show(ctx, |ui| {
+                let lines = logging::recent_lines();
 
-        ui.separator();
-        ui.style_mut().spacing.interact_size.x = 60.0;
-        ui.heading("Steering Wheel");
-        ui.add(
-            egui::Slider::new(&mut config.range, 30.0..=1800.0)
-                .step_by(30.0)
-                .custom_formatter(|v, _| format!("±{v:.0}°"))
-                .text("Range"),
-        );
+                ui.horizontal(|ui| {
+                    let mut level = logging::level();
+                    egui::ComboBox::new("log_level", "Log Level")
+                        .selected_text(level.to_string())
+                        .show_ui(ui, |ui| {
+                            for option in LEVELS {
+                                ui.selectable_value(&mut level, option, option.to_string());
+                            }
+                        });
 
-        ui.add(
-            egui::Slider::new(&mut config.horn_radius, 0.1..=1.0)
-                .step_by(0.1)
-                .text("Horn Radius"),
-        );
+                    if level != logging::level() {
+                        logging::set_level(level);
+                    }
 
-        let base_radius_response = ui.add(
-            egui::Slider::new(&mut config.base_radius, 0.0..=1.0)
-                .step_by(0.1)
-                .text("Base Radius"),
-        );
+                    if ui.button("Copy").clicked() {
+                        ctx.copy_text(lines.join("\n"));
+                    }
+                });
 
-        let base_radius_changing = base_radius_response.dragged() || base_radius_response.hovered();
-        self.base_radius_selection = base_radius_changing.then_some(config.base_radius);
+                ui.separator();
+
+                egui::ScrollArea::both()
+                    .stick_to_bottom(true)
+                    .auto_shrink(false)
+                    .show(ui, |ui| {
+                        for line in &lines {
+                            ui.monospace(line);
+                        }
+                    });
+            });
+    }
+
+    fn draw_tick_timing(&mut self, ctx: &Context, state: &State) {
+        const BINS: usize = 40;
+        /// Intervals beyond this many periods all land in the last bin.
+        const RANGE_PERIODS: f32 = 3.0;
+        /// Intervals longer than this many periods count as stalls.
+        const STALL_PERIODS: f32 = 2.0;
+
+        let rate = state.config.update_frequency * state.config.polls_per_update();
+        let period = 1.0 / rate as f32;
+        let intervals = &state.tick_intervals;
+
+        egui::Window::new("Tick Timing")
+            .open(&mut self.show_tick_timing)
+            .default_size([400.0, 200.0])
+            .show(ctx, |ui| {
+                if intervals.is_empty() {
+                    ui.label("No ticks recorded yet.");
+                    return;
+                }
+
+                let mut bins = [0usize; BINS];
+                let mut stalls = 0;
+                for interval in intervals {
+                    let periods = interval.as_secs_f32() / period;
+                    let bin = (periods / RANGE_PERIODS * BINS as f32) as usize;
+                    bins[bin.min(BINS - 1)] += 1;
+
+                    if periods > STALL_PERIODS {
+                        stalls += 1;
+                    }
+                }
+
+                let mean = intervals.iter().sum::<Duration>() / intervals.len() as u32;
+                let max = intervals.iter().max().copied().unwrap_or_default();
+
+                ui.label(format!(
+                    "Last {} wakeups at {rate} Hz (expected {:.3} ms)",
+                    intervals.len(),
+                    period * 1000.0
+                ));
+                ui.label(format!(
+                    "Mean: {:.3} ms, max: {:.3} ms, stalls over {STALL_PERIODS}x: {stalls}",
+                    mean.as_secs_f64() * 1000.0,
+                    max.as_secs_f64() * 1000.0
+                ));
+
+                let (rect, _) = ui.allocate_exact_size(
+                    ui.available_size().max(Vec2::new(200.0, 80.0)),
+                    Sense::hover(),
+                );
+                let painter = ui.painter_at(rect);
+                painter.rect_filled(rect, 0.0, ui.visuals().extreme_bg_color);
+
+                let most = bins.iter().copied().max().unwrap_or(1).max(1);
+                let bin_width = rect.width() / BINS as f32;
+                for (i, &count) in bins.iter().enumerate() {
+                    if count == 0 {
+                        continue;
+                    }
+
+                    // Keep single counts visible next to very tall bars.
+                    let height = (count as f32 / most as f32 * rect.height()).max(1.0);
+                    let left = rect.left() + i as f32 * bin_width;
+                    let colour = if i as f32 / BINS as f32 * RANGE_PERIODS >= STALL_PERIODS {
+                        Color32::RED
+                    } else {
+                        Color32::LIGHT_BLUE
+                    };
+
+                    painter.rect_filled(
+                        Rect::from_min_max(
+                            Pos2::new(left + 1.0, rect.bottom() - height),
+                            Pos2::new(left + bin_width - 1.0, rect.bottom()),
+                        ),
+                        0.0,
+                        colour,
+                    );
+                }
+
+                // Mark the expected interval.
+                let expected_x = rect.left() + rect.width() / RANGE_PERIODS;
+                painter.vline(
+                    expected_x,
+                    rect.y_range(),
+                    Stroke::new(1.0, Color32::YELLOW),
+                );
+            });
+    }
+
+    fn draw_mapping_debug(&mut self, ctx: &Context, state: &State) {
+        let mapping = &state.config.mapping;
+        let physical_size = state.source.as_ref().and_then(|s| s.physical_size());
+
+        egui::Window::new("Mapping Debug")
+            .open(&mut self.show_mapping_debug)
+            .show(ctx, |ui| {
+                egui::Grid::new("mapping_debug")
+                    .striped(true)
+                    .show(ui, |ui| {
+                        ui.strong("Source");
+                        ui.strong("Input");
+                        ui.strong("Output");
+                        ui.end_row();
+
+                        for (name, (x, y)) in LANDMARKS {
+                            let (out_x, out_y) = mapping.transform(x, y, physical_size);
+                            ui.label(name);
+                            ui.monospace(format!("({x:+.2}, {y:+.2})"));
+                            ui.monospace(format!("({out_x:+.3}, {out_y:+.3})"));
+                            ui.end_row();
+                        }
+                    });
+
+                ui.separator();
+                if ui
+                    .button("Copy Table")
+                    .on_hover_text(
+                        "Copy the outputs for a grid of inputs, to share in a bug report.",
+                    )
+                    .clicked()
+                {
+                    ctx.copy_text(mapping.debug_table(5, physical_size));
+                }
+            });
+    }
+
+    /// Multiplier applied to the wheel angle when drawing it.
+    fn wheel_display_sign(&self) -> f32 {
+        if self.invert_wheel_display { -1.0 } else { 1.0 }
+    }
+
+    /// Ease the angle the steer bar is drawn at towards the wheel's latest angle.
+    /// Purely cosmetic.
+    fn smooth_bar_angle(&mut self, ctx: &Context, angle: f32) -> f32 {
+        const TIME_CONSTANT: f32 = 0.03;
+
+        if !self.smooth_steer_bar {
+            self.smoothed_bar_angle = None;
+            return angle;
+        }
+
+        let dt = ctx.input(|i| i.stable_dt);
+        let alpha = 1.0 - (-dt / TIME_CONSTANT).exp();
+        let smoothed = match self.smoothed_bar_angle {
+            Some(prev) => prev + (angle - prev) * alpha,
+            None => angle,
+        };
+
+        self.smoothed_bar_angle = Some(smoothed);
+        smoothed
+    }
+
+    fn smooth_pen(&mut self, ctx: &Context, pen: Option<Pen>) -> Option<Pen> {
+        const TIME_CONSTANT: f32 = 0.03;
+
+        let Some(mut pen) = pen else {
+            self.smoothed_pen_pos = None;
+            return None;
+        };
+
+        let target = Vec2::new(pen.x, pen.y);
+        if !self.smooth_pen_dot {
+            self.smoothed_pen_pos = Some(target);
+            return Some(pen);
+        }
+
+        let dt = ctx.input(|i| i.stable_dt);
+        let alpha = 1.0 - (-dt / TIME_CONSTANT).exp();
+        let pos = match self.smoothed_pen_pos {
+            Some(prev) => prev + (target - prev) * alpha,
+            None => target,
+        };
+
+        self.smoothed_pen_pos = Some(pos);
+        pen.x = pos.x;
+        pen.y = pos.y;
+
+        Some(pen)
+    }
+
+    /// Remember a feedback torque, returning the recent average and its (min, max) band.
+    /// Purely cosmetic.
+    fn record_ff_torque(&mut self, torque: f32) -> (f32, (f32, f32)) {
+        const WINDOW: usize = 30;
+
+        if self.ff_history.len() >= WINDOW {
+            self.ff_history.pop_front();
+        }
+        self.ff_history.push_back(torque);
+
+        let average = self.ff_history.iter().sum::<f32>() / self.ff_history.len() as f32;
+        let band = self
+            .ff_history
+            .iter()
+            .fold((f32::INFINITY, f32::NEG_INFINITY), |(lo, hi), &t| {
+                (lo.min(t), hi.max(t))
+            });
+
+        (average, band)
+    }
+
+    fn draw_controls_footer(&mut self, ui: &mut Ui, state: &mut State) {
+        ui.add_space(10.0);
+        let width = ui.clip_rect().width() * 0.46;
+
+        let source_btn = egui::Button::new(RichText::new("Reset Source").color(
+            if self.dirty_source_config {
+                Color32::ORANGE
+            } else {
+                Color32::WHITE
+            },
+        ))
+        .min_size(Vec2::new(width, 0.0));
+
+        let device_btn = egui::Button::new(RichText::new("Reset Device").color(
+            if self.dirty_device_config {
+                Color32::ORANGE
+            } else {
+                Color32::WHITE
+            },
+        ))
+        .min_size(Vec2::new(width, 0.0));
+
+        ui.horizontal(|ui| {
+            ui.allocate_ui(Vec2::new(width, 0.0), |ui| {
+                ui.set_min_width(width);
+                let (healthy, hover) = source_health(state);
+                draw_health_dot(ui, "Source", healthy).on_hover_text(hover);
+            });
+
+            ui.allocate_ui(Vec2::new(width, 0.0), |ui| {
+                let (healthy, hover) = device_health(state);
+                draw_health_dot(ui, "Device", healthy).on_hover_text(hover);
+            });
+        });
+
+        ui.horizontal(|ui| {
+            if ui.add(source_btn).clicked() {
+                self.request_reset(state, ResetTarget::Source);
+            }
+
+            if ui.add(device_btn).clicked() {
+                self.request_reset(state, ResetTarget::Device);
+            }
+        });
+
+        ui.add_space(5.0);
+
+        ui.horizontal(|ui| {
+            if ui
+                .add(egui::Button::new("Save").min_size(Vec2::new(width, 0.0)))
+                .clicked()
+            {
+                self.save_action = SaveAction::ToCurrentPath;
+            }
+
+            self.should_load |= ui
+                .add(egui::Button::new("Load...").min_size(Vec2::new(width, 0.0)))
+                .clicked()
+        });
+
+        ui.horizontal_wrapped(|ui| {
+            ui.label("Saving to:");
+            ui.add(egui::Label::new(self.save_path.display().to_string()).selectable(true))
+                .on_hover_text("Where Save writes the configuration. Select it to copy.");
+        });
+    }
+
+    fn draw_controls(&mut self, state: &mut State, ui: &mut Ui) {
+        let config = &mut state.config;
+
+        const UPDATE_FREQUENCY_PRESETS: [u32; 8] = [5, 30, 50, 60, 100, 125, 500, 1000];
+        // Beyond this, sleeping between ticks is too coarse to keep up on some systems.
+        const MAX_RELIABLE_TIMER_RATE: u32 = 1000;
+
+        let custom = self.custom_update_frequency
+            || !UPDATE_FREQUENCY_PRESETS.contains(&config.update_frequency);
+
+        egui::ComboBox::new("update_freq", "Update Frequency")
+            .selected_text(if custom {
+                "Custom".to_string()
+            } else {
+                format!("{} Hz", config.update_frequency)
+            })
+            .show_ui(ui, |ui| {
+                for freq in UPDATE_FREQUENCY_PRESETS {
+                    if ui
+                        .selectable_value(&mut config.update_frequency, freq, format!("{freq} Hz"))
+                        .clicked()
+                    {
+                        self.custom_update_frequency = false;
+                    }
+                }
+
+                if ui.selectable_label(custom, "Custom...").clicked() {
+                    self.custom_update_frequency = true;
+                }
+            });
+
+        if custom {
+            ui.add(
+                egui::DragValue::new(&mut config.update_frequency)
+                    .speed(1)
+                    .range(1..=2000)
+                    .suffix(" Hz"),
+            );
+        }
+
+        ui.checkbox(&mut config.fixed_timestep, "Fixed timestep")
+            .on_hover_text(
+                "Step the physics by however much real time has passed, in fixed increments, \
+                so that the wheel feels the same when updates arrive late or early.",
+            );
+
+        if config.update_frequency * config.polls_per_update() > MAX_RELIABLE_TIMER_RATE {
+            ui.colored_label(
+                Color32::YELLOW,
+                "This rate may not be achievable on all systems.",
+            );
+        }
+
+        ui.horizontal(|ui| {
+            ui.add(
+                egui::DragValue::new(&mut config.event_poll_frequency)
+                    .speed(1)
+                    .range(5..=1000)
+                    .suffix(" Hz"),
+            );
+            ui.label("Min. Event Poll Frequency").on_hover_text(
+                "Device events, such as force-feedback uploads from the game, are serviced \
+                at least this often, even if the update frequency is lower.",
+            );
+        });
+
+        ui.separator();
+        ui.style_mut().spacing.interact_size.x = 60.0;
+        ui.heading("Steering Wheel");
+        ui.add(
+            egui::Slider::new(&mut config.range, 30.0..=1800.0)
+                .step_by(30.0)
+                .custom_formatter(|v, _| format!("±{v:.0}°"))
+                .text("Range"),
+        );
+
+        // Until it is moved, the individual settings are left as they are.
+        let chosen = config.smoothness.is_some();
+        let mut smoothness = config.smoothness.unwrap_or(0.0);
+        let smoothness_response = ui
+            .add(
+                egui::Slider::new(&mut smoothness, 0.0..=1.0)
+                    .custom_formatter(|v, _| {
+                        if chosen {
+                            format!("{:.0}%", v * 100.0)
+                        } else {
+                            "Custom".into()
+                        }
+                    })
+                    .text("Responsive ↔ Smooth"),
+            )
+            .on_hover_text(
+                "Trade latency for smoothness in one go. Sets the slider easing, feedback slew \
+                and output smoothing, as well as the pen dot and steer bar smoothing. \
+                Each can still be adjusted on its own afterwards. \
+                Nothing is changed until this is moved.",
+            );
+        if smoothness_response.changed() {
+            config.smoothness = Some(smoothness);
+            let device_smoothing = config.device_smoothing;
+            config.apply_smoothness();
+            self.dirty_device_config |= config.device_smoothing != device_smoothing;
+            self.smooth_pen_dot = smoothness >= 0.5;
+            self.smooth_steer_bar = smoothness >= 0.5;
+        }
+
+        egui::ComboBox::new("lock_behavior", "At Lock")
+            .selected_text(config.lock_behavior.to_string())
+            .show_ui(ui, |ui| {
+                ui.selectable_value(
+                    &mut config.lock_behavior,
+                    config::LockBehavior::HardStop,
+                    "Hard stop",
+                );
+                ui.selectable_value(
+                    &mut config.lock_behavior,
+                    config::LockBehavior::Bounce,
+                    "Bounce",
+                );
+            })
+            .response
+            .on_hover_text("What happens when the free wheel hits the end of its range.");
+
+        egui::ComboBox::new("steering_model", "Steering Model")
+            .selected_text(config.steering_model.to_string())
+            .show_ui(ui, |ui| {
+                ui.selectable_value(
+                    &mut config.steering_model,
+                    config::SteeringModel::Radial,
+                    "Radial",
+                );
+                ui.selectable_value(
+                    &mut config.steering_model,
+                    config::SteeringModel::Planar,
+                    "Planar",
+                );
+            })
+            .response
+            .on_hover_text(
+                "Radial: draw circles around the centre to turn the wheel. \
+                Allows many turns lock-to-lock, and pressing the centre sounds the horn.\n\n\
+                Planar: the pen's horizontal position sets the wheel angle directly. \
+                Quicker to learn, but less precise over large ranges, and there is no horn.",
+            );
+        ui.add_enabled(
+            config.steering_model == config::SteeringModel::Planar,
+            egui::Checkbox::new(&mut config.relative_engage, "Relative engage"),
+        )
+        .on_hover_text(
+            "Keep the wheel where it is when the pen lands, and steer relative to that point \
+            instead of snapping to the pen's position.",
+        );
+
+        // Devices leave out the horn button while it is off.
+        self.dirty_device_config |= ui
+            .checkbox(&mut config.horn_enabled, "Horn")
+            .on_hover_text("Turn off to never sound the horn, wherever the pen is pressed.")
+            .changed();
+
+        if config.horn_enabled {
+            ui.add(
+                egui::Slider::new(&mut config.horn_radius, 0.1..=1.0)
+                    .step_by(0.1)
+                    .text("Horn Radius"),
+            );
+
+            ui.add(
+                egui::Slider::new(&mut config.horn_hysteresis, 0.0..=0.5)
+                    .step_by(0.05)
+                    .text("Horn Hysteresis"),
+            )
+            .on_hover_text(
+                "How far past the horn radius the pen must slide before the horn is released.",
+            );
+
+            ui.add(
+                egui::Slider::new(&mut config.horn_cooldown, 0.0..=1.0)
+                    .step_by(0.05)
+                    .suffix(" s")
+                    .text("Horn Cooldown"),
+            )
+            .on_hover_text(
+                "After letting go of the wheel, pressing the centre continues steering \
+                instead of sounding the horn until this much time has passed.",
+            );
+
+            ui.add(
+                egui::Slider::new(&mut config.horn_dwell_ms, 0..=500)
+                    .step_by(10.0)
+                    .suffix(" ms")
+                    .text("Horn Dwell"),
+            )
+            .on_hover_text(
+                "How long the pen must rest in the centre before the horn sounds, \
+                so that quickly passing through it doesn't honk.",
+            );
+        }
+
+        let base_radius_response = ui.add(
+            egui::Slider::new(&mut config.base_radius, 0.0..=1.0)
+                .step_by(0.1)
+                .text("Base Radius"),
+        );
+
+        let base_radius_changing = base_radius_response.dragged() || base_radius_response.hovered();
+        self.base_radius_selection = base_radius_changing.then_some(config.base_radius);
 
         const BASE_RADIUS_TOOLTIP: &str = "Minimum radius for angular \
         displacement calculations.\nCircling the pen closer than this radius \
@@ -381,6 +1383,15 @@ impl GuiApp {
         than intended.";
         base_radius_response.on_hover_text(BASE_RADIUS_TOOLTIP);
 
+        ui.add(
+            egui::Slider::new(&mut config.sensitivity, 0.1..=5.0)
+                .step_by(0.05)
+                .text("Sensitivity"),
+        )
+        .on_hover_text(
+            "How far the wheel turns for a given pen movement, independent of the range.",
+        );
+
         ui.style_mut().spacing.interact_size.x = 150.0;
 
         ui.horizontal(|ui| {
@@ -406,9 +1417,46 @@ impl GuiApp {
             });
         });
 
+        ui.horizontal(|ui| {
+            ui.label("Quadratic friction coefficient: ");
+            ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
+                ui.add(
+                    egui::DragValue::new(&mut config.friction_quadratic)
+                        .speed(0.1)
+                        .range(0.0..=100.0),
+                );
+            });
+        });
+
+        ui.horizontal(|ui| {
+            ui.label("Release damping: ");
+            ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
+                ui.add(
+                    egui::DragValue::new(&mut config.release_damping)
+                        .speed(0.5)
+                        .range(0.0..=200.0),
+                )
+                .on_hover_text(
+                    "Extra friction just after letting go of the wheel, fading out over half \
+                    a second, so that it slows down predictably when released mid-turn. \
+                    0 leaves it alone.",
+                );
+            });
+        });
+
         ui.horizontal(|ui| {
             ui.label("Spring stiffness:");
             ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
+                ui.add(
+                    egui::DragValue::new(&mut config.max_spring_torque)
+                        .speed(0.5)
+                        .range(0.0..=1000.0)
+                        .suffix(" Nm max"),
+                )
+                .on_hover_text(
+                    "Caps the spring's pull, so letting go far off centre doesn't snap back \
+                    violently. 0 is unlimited.",
+                );
                 ui.add(
                     egui::DragValue::new(&mut config.spring)
                         .speed(0.5)
@@ -418,6 +1466,68 @@ impl GuiApp {
             });
         });
 
+        ui.add(
+            egui::Slider::new(&mut config.spring_exp, 0.5..=4.0)
+                .step_by(0.1)
+                .text("Spring Curve"),
+        )
+        .on_hover_text(
+            "1 centres in proportion to the angle. Higher values centre gently near \
+            straight ahead and more strongly towards lock.",
+        );
+
+        ui.horizontal(|ui| {
+            ui.label("Caster:");
+            ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
+                ui.add(
+                    egui::DragValue::new(&mut config.caster)
+                        .speed(0.5)
+                        .range(0.0..=100.0)
+                        .suffix(" Nm/rad"),
+                )
+                .on_hover_text(
+                    "Extra centring while the wheel turns away from centre, so it returns \
+                    more easily than it leaves. 0 makes both directions feel the same.",
+                );
+            });
+        });
+
+        ui.horizontal(|ui| {
+            ui.label("Detents:");
+            ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
+                ui.add(
+                    egui::DragValue::new(&mut config.detent_strength)
+                        .speed(0.1)
+                        .range(0.0..=100.0)
+                        .suffix(" Nm/rad"),
+                );
+                ui.add(egui::DragValue::new(&mut config.detents).range(0..=100))
+                    .on_hover_text(
+                        "Number of notches the free wheel settles into. 0 disables them.",
+                    );
+            });
+        });
+
+        ui.horizontal(|ui| {
+            ui.label("Centre assist:");
+            ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
+                ui.add(
+                    egui::DragValue::new(&mut config.center_assist_strength)
+                        .speed(0.1)
+                        .range(0.0..=100.0)
+                        .suffix(" Nm/rad"),
+                )
+                .on_hover_text("How strongly a slow wheel is pulled straight. 0 disables it.");
+                ui.add(
+                    egui::DragValue::new(&mut config.center_assist_band)
+                        .speed(0.1)
+                        .range(0.0..=45.0)
+                        .suffix("°"),
+                )
+                .on_hover_text("How far either side of straight ahead the assist acts.");
+            });
+        });
+
         ui.horizontal(|ui| {
             ui.label("Max feedback torque: ");
             ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
@@ -430,54 +1540,208 @@ impl GuiApp {
             });
         });
 
-        if self.show_wheel {
-            let half_range = config.half_range_rad();
-
-            ui.separator();
-            ui.style_mut().spacing.interact_size.x = 40.0;
+        if let Some(device) = &state.device
+            && device.get_feedback().is_some()
+        {
+            ui.label(format!(
+                "Feedback torque: {}",
+                format_torque(
+                    state.wheel.feedback_torque,
+                    config.max_torque,
+                    self.torque_as_percent
+                )
+            ));
+        }
+
+        ui.horizontal(|ui| {
+            ui.label("Max feedback slew: ");
+            ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
+                ui.add(
+                    egui::DragValue::new(&mut config.feedback_slew)
+                        .speed(10.0)
+                        .range(0.0..=100000.0)
+                        .suffix(" Nm/s"),
+                )
+                .on_hover_text("How quickly the feedback torque may change. 0 is unlimited.");
+            });
+        });
+
+        ui.horizontal(|ui| {
+            ui.label("Max feedback rise: ");
+            if state.wheel.rise_limited {
+                ui.colored_label(Color32::ORANGE, "limiting");
+            }
+            ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
+                ui.add(
+                    egui::DragValue::new(&mut config.feedback_rise_limit)
+                        .speed(10.0)
+                        .range(0.0..=100000.0)
+                        .suffix(" Nm/s"),
+                )
+                .on_hover_text(
+                    "Safety limit on how quickly the feedback torque may grow, \
+                    in case a game's effect runs away. 0 disables it.",
+                );
+            });
+        });
+
+        if ui
+            .checkbox(&mut config.feedback_invert, "Invert feedback")
+            .on_hover_text(
+                "Flip the direction of the game's force, if it makes the wheel oscillate.",
+            )
+            .changed()
+        {
+            info!("Force feedback inverted: {}", config.feedback_invert);
+        }
+
+        ui.checkbox(&mut config.center_when_idle, "Centre when idle")
+            .on_hover_text("Return the wheel to straight ahead while there is no pen input.");
+
+        if self.show_wheel {
+            let half_range = config.half_range_rad();
+
+            ui.separator();
+            ui.style_mut().spacing.interact_size.x = 40.0;
+            let mut angle = state.wheel.manual_target.unwrap_or(state.wheel.angle);
+            let angle_response = ui.add(
+                egui::Slider::new(&mut angle, -half_range..=half_range)
+                    .drag_value_speed(1.0f64.to_radians())
+                    .custom_formatter(|v, _| {
+                        format_angle(v as f32, half_range, self.angle_as_lock_percent, 1)
+                    })
+                    .text("Angle"),
+            );
+
+            // While dragging, the physics ease towards the slider instead of fighting it.
+            if angle_response.dragged() {
+                state.wheel.manual_target = Some(angle);
+            } else {
+                state.wheel.manual_target = None;
+                if angle_response.changed() {
+                    state.wheel.angle = angle;
+                }
+            }
+
+            if angle_response.hovered() {
+                let nudge = take_scroll_nudge(ui);
+                state.wheel.angle = math::clamp_symmetric(half_range, state.wheel.angle + nudge);
+            }
+
+            ui.add(
+                egui::Slider::new(&mut config.slider_easing, 0.0..=0.5)
+                    .step_by(0.01)
+                    .suffix(" s")
+                    .text("Slider Easing"),
+            )
+            .on_hover_text(
+                "How smoothly the wheel follows the angle slider while it is dragged. 0 snaps.",
+            );
+
+            ui.checkbox(&mut state.freeze_physics, "Freeze physics")
+                .on_hover_text(
+                    "Stop simulating the wheel while it is not being dragged.\n\
+                    Useful for telling input problems apart from physics problems.",
+                );
+        }
+
+        ui.separator();
+        ui.heading("Input");
+
+        ui.horizontal(|ui| {
+            ui.add(
+                egui::DragValue::new(&mut config.pressure_threshold)
+                    .speed(1)
+                    .range(0..=2048)
+                    .clamp_existing_to_range(true),
+            );
+            ui.label("Pen Pressure Threshold");
+        });
+
+        ui.horizontal(|ui| {
+            ui.add(
+                egui::DragValue::new(&mut config.clutch_pressure)
+                    .speed(1)
+                    .range(0..=65535)
+                    .custom_formatter(|n, _| {
+                        if n == 0.0 {
+                            "Off".into()
+                        } else {
+                            n.to_string()
+                        }
+                    }),
+            );
+            ui.label("Clutch Pressure").on_hover_text(
+                "Pressing harder than this holds the clutch binding, while still steering. \
+                0 turns it off.",
+            );
+        });
+
+        ui.horizontal(|ui| {
             ui.add(
-                egui::Slider::new(
-                    &mut state.wheel.angle,
-                    -half_range..=half_range,
-                )
-                .drag_value_speed(1.0f64.to_radians())
-                .custom_formatter(|v, _| format!("{:.1}°", v.to_degrees()))
-                .text("Angle"),
+                egui::DragValue::new(&mut config.mapping.pressure_min)
+                    .speed(1)
+                    .range(0..=config.mapping.pressure_max.saturating_sub(1)),
             );
-        }
-
-        ui.separator();
-        ui.heading("Input");
+            ui.label("Min Pen Pressure").on_hover_text(
+                "Raw pressure at and below which the pen reads as not pressing. \
+                Useful for pens which never report zero.",
+            );
+        });
 
         ui.horizontal(|ui| {
             ui.add(
-                egui::DragValue::new(&mut config.pressure_threshold)
+                egui::DragValue::new(&mut config.mapping.pressure_max)
                     .speed(1)
-                    .range(0..=2048)
-                    .clamp_existing_to_range(true),
+                    .range(1..=65535),
             );
-            ui.label("Pen Pressure Threshold");
+            ui.label("Max Pen Pressure");
+            ui.checkbox(&mut config.mapping.pressure_invert, "Inverted")
+                .on_hover_text("For devices which report lower pressure for pressing harder.");
         });
 
-        let old_source = config.source;
-        egui::ComboBox::new("source", "Input Source")
-            .selected_text(old_source.to_string())
+        if let Some(pen) = &state.pen {
+            let percentage = 100.0 * config.mapping.pressure_level(pen.pressure);
+            ui.label(format!("Pressure: {percentage:.0}%"));
+        }
+
+        select_pen_button(ui, "Recenter Button", &mut config.recenter_button).on_hover_text(
+            "Pen button which recentres the wheel when pressed. \
+            For evdev tablets, bits 0 to 2 are the stylus buttons.",
+        );
+        select_pen_button(ui, "Engage Button", &mut config.engage_button).on_hover_text(
+            "Pen button which must be held for the pen to turn the wheel. \
+            Let go of it to move the pen without steering. None always steers.",
+        );
+
+        egui::ComboBox::new("eraser_action", "Eraser Action")
+            .selected_text(config.eraser_action.to_string())
             .show_ui(ui, |ui| {
-                ui.selectable_value(&mut config.source, config::Source::None, "Disabled");
                 ui.selectable_value(
-                    &mut config.source,
-                    config::Source::Net,
-                    "Network (over UDP)",
+                    &mut config.eraser_action,
+                    config::EraserAction::Steer,
+                    "Steer",
                 );
-                #[cfg(target_os = "windows")]
                 ui.selectable_value(
-                    &mut config.source,
-                    config::Source::Wintab,
-                    "Wacom Wintab (Windows)",
+                    &mut config.eraser_action,
+                    config::EraserAction::Horn,
+                    "Horn",
                 );
-                #[cfg(target_os = "linux")]
-                ui.selectable_value(&mut config.source, config::Source::Evdev, "Evdev (Linux)");
-            });
+            })
+            .response
+            .on_hover_text("What touching down with the eraser end of the pen does, if supported.");
+
+        ui.add_enabled(
+            config.horn_enabled,
+            egui::Checkbox::new(&mut config.second_contact_horn, "Second finger honks"),
+        )
+        .on_hover_text(format!(
+            "On touch devices, sound the horn while another finger touches, \
+            without interrupting steering. Network senders set bit {SECOND_CONTACT_BIT}."
+        ));
+
+        let old_source = config.source;
+        select_source(ui, "source", "Input Source", &mut config.source);
 
         self.dirty_source_config |= config.source != old_source;
 
@@ -490,6 +1754,44 @@ impl GuiApp {
                     ui.label("Listen to: ");
                     ui.text_edit_singleline(&mut config.net_sock_addr);
                 });
+                self.dirty_source_config |= ui
+                    .checkbox(&mut config.net_hold_last, "Hold last sample")
+                    .on_hover_text(
+                        "Keep reporting the last pen sample when packets arrive irregularly, \
+                        instead of reporting nothing.",
+                    )
+                    .changed();
+                self.dirty_source_config |= ui
+                    .checkbox(&mut config.net_lock_sender, "Lock onto first sender")
+                    .on_hover_text(
+                        "Ignore other senders until the current one stops sending for a while.",
+                    )
+                    .changed();
+                edit_poll_rate(ui, &mut config.net_poll_rate);
+                ui.horizontal(|ui| {
+                    ui.label("Max packets per poll:");
+                    self.dirty_source_config |= ui
+                        .add(
+                            egui::DragValue::new(&mut config.net_max_drain)
+                                .range(0..=10_000)
+                                .custom_formatter(|n, _| {
+                                    if n == 0.0 {
+                                        "Unlimited".into()
+                                    } else {
+                                        n.to_string()
+                                    }
+                                }),
+                        )
+                        .on_hover_text(
+                            "How many queued packets are read at most each time the source is \
+                            polled, so that a burst can't stall an update. 0 reads them all.",
+                        )
+                        .changed();
+                });
+
+                if let Some(peer) = state.source.as_ref().and_then(|s| s.peer()) {
+                    ui.label(format!("Sender: {peer}"));
+                }
             }
             #[cfg(target_os = "windows")]
             config::Source::Wintab => {
@@ -516,8 +1818,8 @@ impl GuiApp {
                                 changed |= ui
                                     .selectable_value(
                                         &mut config.preferred_tablet,
-                                        Some(dev.clone()),
-                                        dev,
+                                        Some(dev.name.clone()),
+                                        dev.label(),
                                     )
                                     .clicked();
                             }
@@ -530,105 +1832,728 @@ impl GuiApp {
                         }
                     });
 
-                if changed {
-                    self.dirty_source_config = true;
-                }
+                if changed {
+                    self.dirty_source_config = true;
+                }
+
+                ui.horizontal(|ui| {
+                    ui.label("Device path:");
+                    let mut path = config
+                        .evdev_path
+                        .as_ref()
+                        .map(|path| path.display().to_string())
+                        .unwrap_or_default();
+                    let response = ui
+                        .add(
+                            egui::TextEdit::singleline(&mut path)
+                                .hint_text("/dev/input/by-id/...")
+                                .desired_width(200.0),
+                        )
+                        .on_hover_text(
+                            "Open this device node directly instead of looking for the \
+                            preferred tablet by name. Leave empty to go by name.",
+                        );
+                    if response.changed() {
+                        let path = path.trim();
+                        config.evdev_path = (!path.is_empty()).then(|| path.into());
+                        self.dirty_source_config = true;
+                    }
+                });
+
+                edit_poll_rate(ui, &mut config.evdev_poll_rate);
+
+                // Offer the axes of the tablet in use, or failing that, the preferred one.
+                let tablet_name = state
+                    .source
+                    .as_ref()
+                    .and_then(|s| s.device_name())
+                    .or(config.preferred_tablet.as_deref());
+                let available_axes = self
+                    .evdev_available_devices
+                    .iter()
+                    .flatten()
+                    .find(|dev| tablet_name.is_some_and(|name| dev.name.contains(name)))
+                    .map(|dev| dev.axes.as_slice())
+                    .unwrap_or_default();
+                for (label, axis) in [
+                    ("Steering X Axis", &mut config.evdev_x_axis),
+                    ("Steering Y Axis", &mut config.evdev_y_axis),
+                ] {
+                    changed = false;
+                    egui::ComboBox::new(label, label)
+                        .selected_text(axis.as_deref().unwrap_or("Default"))
+                        .show_ui(ui, |ui| {
+                            changed |= ui.selectable_value(axis, None, "Default").clicked();
+                            for name in available_axes {
+                                changed |= ui
+                                    .selectable_value(axis, Some(name.clone()), name)
+                                    .clicked();
+                            }
+                        })
+                        .response
+                        .on_hover_text(
+                            "Which of the tablet's axes is used as this coordinate. \
+                            Default uses its usual position axes.",
+                        );
+
+                    if changed {
+                        self.dirty_source_config = true;
+                    }
+                }
+
+                if let Some(name) = state.source.as_ref().and_then(|s| s.device_name()) {
+                    ui.label(format!("Using: {name}"));
+                }
+            }
+        }
+
+        let old_merge = (config.merge_source, config.merge_policy);
+        select_source(ui, "merge_source", "Merge With", &mut config.merge_source);
+        if config.merge_source != config::Source::None && config.merge_source == config.source {
+            ui.colored_label(
+                Color32::YELLOW,
+                "Can't merge with another source of the same kind.",
+            );
+        } else if config.merge_source != config::Source::None {
+            egui::ComboBox::new("merge_policy", "Taken From Both")
+                .selected_text(config.merge_policy.to_string())
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(
+                        &mut config.merge_policy,
+                        config::MergePolicy::Buttons,
+                        "Buttons",
+                    );
+                    ui.selectable_value(
+                        &mut config.merge_policy,
+                        config::MergePolicy::PressureAndButtons,
+                        "Pressure and buttons",
+                    );
+                })
+                .response
+                .on_hover_text(
+                    "Buttons held on either source count. Pressure may instead come from the \
+                    second source, e.g. a phone used as a pedal. Position always comes from \
+                    the input source.",
+                );
+        }
+        self.dirty_source_config |= (config.merge_source, config.merge_policy) != old_merge;
+
+        ui.separator();
+        ui.heading("Mapping");
+        ui.style_mut().spacing.interact_size.x = 65.0;
+        let map = &mut config.mapping;
+        egui::ComboBox::new("map-mode", "Input Area")
+            .selected_text(match map.mode {
+                MapMode::Rect => "Rectangle",
+                MapMode::Quad => "Four corners",
+                MapMode::Physical => "Millimetres",
+            })
+            .show_ui(ui, |ui| {
+                ui.selectable_value(&mut map.mode, MapMode::Rect, "Rectangle");
+                ui.selectable_value(&mut map.mode, MapMode::Quad, "Four corners");
+                ui.selectable_value(&mut map.mode, MapMode::Physical, "Millimetres");
+            });
+        let physical_size = state.source.as_ref().and_then(|s| s.physical_size());
+        match map.mode {
+            MapMode::Rect => {
+                ui.horizontal(|ui| {
+                    ui.label("Input:");
+                    ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
+                        ui.add(egui::DragValue::new(&mut map.min_in_x).speed(0.1));
+                        ui.add(egui::DragValue::new(&mut map.min_in_y).speed(0.1));
+                        ui.add(egui::DragValue::new(&mut map.max_in_x).speed(0.1));
+                        ui.add(egui::DragValue::new(&mut map.max_in_y).speed(0.1));
+                    });
+                });
+            }
+            MapMode::Quad => {
+                const CORNER_NAMES: [&str; 4] = [
+                    "Min X, min Y:",
+                    "Max X, min Y:",
+                    "Max X, max Y:",
+                    "Min X, max Y:",
+                ];
+
+                for (name, corner) in CORNER_NAMES.iter().zip(map.corners.iter_mut()) {
+                    ui.horizontal(|ui| {
+                        ui.label(*name);
+                        ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
+                            ui.add(egui::DragValue::new(&mut corner.1).speed(0.01));
+                            ui.add(egui::DragValue::new(&mut corner.0).speed(0.01));
+                        });
+                    });
+                }
+            }
+            MapMode::Physical => {
+                let (min_x, min_y, max_x, max_y) = &mut map.area_mm;
+                ui.horizontal(|ui| {
+                    ui.label("Input (mm from centre):");
+                    ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
+                        ui.add(egui::DragValue::new(min_x).speed(1.0));
+                        ui.add(egui::DragValue::new(min_y).speed(1.0));
+                        ui.add(egui::DragValue::new(max_x).speed(1.0));
+                        ui.add(egui::DragValue::new(max_y).speed(1.0));
+                    });
+                });
+
+                if physical_size.is_none() {
+                    ui.colored_label(
+                        Color32::YELLOW,
+                        "The source does not report its size; using the rectangle input area.",
+                    );
+                }
+            }
+        }
+        if let Some((width, height)) = physical_size {
+            ui.label(format!("Tablet size: {width:.0} x {height:.0} mm"));
+        }
+        ui.horizontal(|ui| {
+            ui.label("Output:");
+            ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
+                ui.add(egui::DragValue::new(&mut map.min_out_x).speed(0.1));
+                ui.add(egui::DragValue::new(&mut map.min_out_y).speed(0.1));
+                ui.add(egui::DragValue::new(&mut map.max_out_x).speed(0.1));
+                ui.add(egui::DragValue::new(&mut map.max_out_y).speed(0.1));
+            });
+        });
+        ui.horizontal(|ui| {
+            ui.label("Clamp:");
+            ui.radio_value(&mut map.clamp_shape, ClampShape::Square, "Square");
+            ui.radio_value(&mut map.clamp_shape, ClampShape::Circle, "Circle")
+                .on_hover_text("Diagonals reach as far as straight up, down, left or right.");
+        });
+        egui::ComboBox::new("map-orient", "Orientation")
+            .selected_text(format!("{:?}", map.orientation))
+            .show_ui(ui, |ui| {
+                ui.selectable_value(&mut map.orientation, MapOrientation::None, "A0");
+                ui.selectable_value(&mut map.orientation, MapOrientation::A90, "A90");
+                ui.selectable_value(&mut map.orientation, MapOrientation::A180, "A180");
+                ui.selectable_value(&mut map.orientation, MapOrientation::A270, "A270");
+            });
+        ui.checkbox(&mut map.invert_x, "Invert X axis");
+        ui.checkbox(&mut map.invert_y, "Invert Y axis");
+        ui.horizontal(|ui| {
+            ui.label("Handedness:");
+            ui.radio_value(&mut map.handedness, Handedness::Right, "Right");
+            ui.radio_value(&mut map.handedness, Handedness::Left, "Left (mirrored)");
+        });
+
+        ui.separator();
+        ui.heading("Output");
+
+        let old_device = config.device;
+        select_device(ui, "device", &mut config.device);
+
+        let mut preset = None;
+        egui::ComboBox::new("game_preset", "Game Preset")
+            .selected_text("Choose...")
+            .show_ui(ui, |ui| {
+                for option in config::GamePreset::ALL {
+                    if ui.selectable_label(false, option.to_string()).clicked() {
+                        preset = Some(option);
+                    }
+                }
+            })
+            .response
+            .on_hover_text(
+                "Set the range, spring, device identity, axes, inversion and bindings to a \
+                starting point for a kind of game. Other settings are kept.",
+            );
+        if let Some(preset) = preset {
+            if config.game_preset_overwrites(preset).is_empty() {
+                self.apply_game_preset(config, preset);
+            } else {
+                self.pending_preset = Some(preset);
+            }
+        }
+
+        if config.device != old_device {
+            self.dirty_device_config = true;
+        }
+
+        if state.device_fallback {
+            ui.colored_label(
+                Color32::YELLOW,
+                "Device could not be created; no output is being sent. Reset the device to retry.",
+            );
+        }
+
+        egui::ComboBox::new("panic_key", "Panic Key")
+            .selected_text(config.panic_key.as_str())
+            .show_ui(ui, |ui| {
+                for key in [
+                    egui::Key::F1,
+                    egui::Key::F2,
+                    egui::Key::F3,
+                    egui::Key::F4,
+                    egui::Key::F5,
+                    egui::Key::F6,
+                    egui::Key::F7,
+                    egui::Key::F8,
+                    egui::Key::F9,
+                    egui::Key::F10,
+                    egui::Key::F11,
+                    egui::Key::F12,
+                ] {
+                    ui.selectable_value(&mut config.panic_key, key.name().to_owned(), key.name());
+                }
+            })
+            .response
+            .on_hover_text(
+                "Pressing this key neutralises the output and stops the wheel until resumed. \
+                It is only seen while this window is focused, not while a game is, \
+                and not when running headless. See also Pause when unfocused.",
+            );
+
+        ui.checkbox(&mut config.pause_when_unfocused, "Pause when unfocused")
+            .on_hover_text(
+                "Neutralise the output and stop the wheel while this window isn't focused, \
+                e.g. after switching to another program. Has no effect without the GUI.",
+            );
+
+        ui.add(
+            egui::Slider::new(&mut config.output_ramp, 0.0..=2.0)
+                .step_by(0.1)
+                .suffix(" s")
+                .text("Output Ramp"),
+        )
+        .on_hover_text(
+            "After the device is created, its output eases in to the current angle over \
+            this long, so a game does not see it jump.",
+        );
+
+        ui.add(
+            egui::Slider::new(&mut config.startup_delay_ms, 0..=10_000)
+                .step_by(100.0)
+                .suffix(" ms")
+                .text("Startup Delay"),
+        )
+        .on_hover_text(
+            "Wait this long at startup before creating the device, for systems where \
+            the driver is not ready straight away. Takes effect on the next launch.",
+        );
+
+        self.dirty_device_config |= ui
+            .add(
+                egui::Slider::new(&mut config.output_center_offset, -0.5..=0.5)
+                    .custom_formatter(|v, _| format!("{:+.1}%", v * 100.0))
+                    .text("Centre Offset"),
+            )
+            .on_hover_text(
+                "Shift where the output rests when the wheel is straight, for games whose \
+                calibration can't be reset. Output is still kept within the axis.",
+            )
+            .changed();
+
+        match old_device {
+            config::Device::None => {
+                ui.colored_label(Color32::YELLOW, "No output available!");
+            }
+            #[cfg(target_os = "linux")]
+            config::Device::UInput => {
+                ui.heading("Virtual Controller: (via uinput)");
+                ui.horizontal(|ui| {
+                    ui.label("Name:");
+                    self.dirty_device_config |=
+                        ui.text_edit_singleline(&mut config.device_name).changed();
+                });
+
+                ui.horizontal(|ui| {
+                    ui.label("Vendor:");
+                    self.dirty_device_config |= edit_u16_hex(
+                        ui,
+                        &mut config.device_vendor,
+                        &mut self.device_vendor_edit_buf,
+                    );
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Product:");
+                    self.dirty_device_config |= edit_u16_hex(
+                        ui,
+                        &mut config.device_product,
+                        &mut self.device_product_edit_buf,
+                    );
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Version:");
+                    self.dirty_device_config |= edit_u16_hex(
+                        ui,
+                        &mut config.device_version,
+                        &mut self.device_version_edit_buf,
+                    );
+                });
+
+                self.dirty_device_config |= ui
+                    .checkbox(&mut config.device_smoothing, "Smooth low resolution output")
+                    .on_hover_text(format!(
+                        "Ease the axis towards new values over a few updates to hide \
+                        stair-stepping. Only applies below a resolution of {}.",
+                        crate::device::uinput::LOW_RESOLUTION
+                    ))
+                    .changed();
+
+                ui.label("Bindings:").on_hover_text(
+                    "Virtual buttons pressed by each input, such as ButtonSouth. Leave empty to unbind.",
+                );
+                let bindings = &mut config.bindings;
+                let [pen_button1, pen_button2, pen_button3] = &mut bindings.pen_buttons;
+                egui::Grid::new("uinput_bindings").show(ui, |ui| {
+                    for (label, name) in [
+                        ("Horn", &mut bindings.horn),
+                        ("Pen button 1", pen_button1),
+                        ("Pen button 2", pen_button2),
+                        ("Pen button 3", pen_button3),
+                        ("Eraser", &mut bindings.eraser),
+                        ("Clutch", &mut bindings.clutch),
+                    ] {
+                        ui.label(label);
+                        self.dirty_device_config |= ui
+                            .add(egui::TextEdit::singleline(name).desired_width(120.0))
+                            .changed();
+                        ui.end_row();
+                    }
+                });
+
+                ui.horizontal(|ui| {
+                    self.dirty_device_config |= ui
+                        .checkbox(&mut config.lean_output, "Lean from tilt on")
+                        .on_hover_text(
+                            "Send how far the pen leans to either side on an extra axis.",
+                        )
+                        .changed();
+                    ui.add_enabled_ui(config.lean_output, |ui| {
+                        egui::ComboBox::new("lean_axis", "axis")
+                            .selected_text(config.lean_axis.to_string())
+                            .show_ui(ui, |ui| {
+                                for axis in config::AuxAxis::ALL {
+                                    self.dirty_device_config |= ui
+                                        .selectable_value(
+                                            &mut config.lean_axis,
+                                            axis,
+                                            axis.to_string(),
+                                        )
+                                        .changed();
+                                }
+                            });
+                    });
+                });
+                if config.horn_enabled {
+                    ui.horizontal(|ui| {
+                        self.dirty_device_config |= ui
+                            .checkbox(&mut config.horn_axis_output, "Horn as axis on")
+                            .on_hover_text(
+                                "Send the horn at full scale on an extra axis instead of pressing \
+                                its button binding, for games which want an analog horn.",
+                            )
+                            .changed();
+                        ui.add_enabled_ui(config.horn_axis_output, |ui| {
+                            egui::ComboBox::new("horn_axis", "axis")
+                                .selected_text(config.horn_axis.to_string())
+                                .show_ui(ui, |ui| {
+                                    for axis in config::AuxAxis::ALL {
+                                        self.dirty_device_config |= ui
+                                            .selectable_value(
+                                                &mut config.horn_axis,
+                                                axis,
+                                                axis.to_string(),
+                                            )
+                                            .changed();
+                                    }
+                                });
+                        });
+                    });
+                }
+                if config.lean_output
+                    && state.raw_pen.as_ref().is_some_and(|pen| pen.tilt.is_none())
+                {
+                    ui.colored_label(Color32::YELLOW, "The source does not report tilt.");
+                }
+
+                if ui
+                    .button("Test FF")
+                    .on_hover_text("Briefly push the wheel as if the game applied a force.")
+                    .clicked()
+                    && let Some(device) = &mut state.device
+                {
+                    device.trigger_test_feedback();
+                }
+            }
+            #[cfg(target_os = "linux")]
+            config::Device::Keys => {
+                ui.heading("Virtual Keyboard: (via uinput)");
+                ui.horizontal(|ui| {
+                    ui.label("Left key:");
+                    self.dirty_device_config |= ui
+                        .add(egui::TextEdit::singleline(&mut config.keys_left).desired_width(80.0))
+                        .changed();
+                    ui.label("Right key:");
+                    self.dirty_device_config |= ui
+                        .add(egui::TextEdit::singleline(&mut config.keys_right).desired_width(80.0))
+                        .changed();
+                });
+
+                self.dirty_device_config |= ui
+                    .add(
+                        egui::Slider::new(&mut config.keys_deadzone, 0.0..=0.5)
+                            .step_by(0.01)
+                            .text("Deadzone"),
+                    )
+                    .changed();
+
+                self.dirty_device_config |= ui
+                    .add(
+                        egui::Slider::new(&mut config.keys_saturation, 0.0..=1.0)
+                            .step_by(0.01)
+                            .text("Saturation"),
+                    )
+                    .on_hover_text(
+                        "Wheel deflection at which the key is held down continuously.\n\
+                        Between the deadzone and this point, the key is tapped for longer \
+                        the further the wheel is turned.",
+                    )
+                    .changed();
+            }
+            #[cfg(target_os = "linux")]
+            config::Device::Mouse => {
+                ui.heading("Virtual Mouse: (via uinput)");
+                ui.horizontal(|ui| {
+                    ui.label("Sensitivity:");
+                    self.dirty_device_config |= ui
+                        .add(
+                            egui::DragValue::new(&mut config.mouse_pixels_per_degree)
+                                .speed(0.1)
+                                .range(-1000.0..=1000.0)
+                                .suffix(" px/°"),
+                        )
+                        .on_hover_text(
+                            "How far the mouse moves sideways for each degree the wheel turns. \
+                            Negative values move it the other way.",
+                        )
+                        .changed();
+                });
+
+                self.dirty_device_config |= ui
+                    .add(
+                        egui::Slider::new(&mut config.mouse_deadzone, 0.0..=45.0)
+                            .step_by(0.5)
+                            .suffix("°")
+                            .text("Deadzone"),
+                    )
+                    .on_hover_text(
+                        "How far either side of centre the wheel turns before the mouse moves.",
+                    )
+                    .changed();
+
+                ui.label("Changing the range also needs the device to be reset.");
+            }
+            #[cfg(target_os = "windows")]
+            config::Device::VigemBus => {
+                ui.heading("Virtual Controller: (via ViGEm)");
+                self.dirty_device_config |= ui
+                    .add(
+                        egui::Slider::new(&mut config.vigem_scale, 0.1..=10.0)
+                            .logarithmic(true)
+                            .text("Stick Scale"),
+                    )
+                    .changed();
+                self.dirty_device_config |= ui
+                    .checkbox(&mut config.vigem_invert, "Invert stick direction")
+                    .changed();
+                ui.horizontal(|ui| {
+                    ui.label("Max output rate:");
+                    self.dirty_device_config |= ui
+                        .add(
+                            egui::DragValue::new(&mut config.vigem_max_rate)
+                                .range(0..=10_000)
+                                .custom_formatter(|n, _| {
+                                    if n == 0.0 {
+                                        "Unlimited".into()
+                                    } else {
+                                        format!("{n} Hz")
+                                    }
+                                }),
+                        )
+                        .on_hover_text(
+                            "Send at most this many updates per second to the driver, \
+                            always with the latest state. 0 sends every change.",
+                        )
+                        .changed();
+                });
+            }
+        }
+    }
+
+    fn draw_setup(&mut self, ctx: &Context, state: &mut State) {
+        let mut start = false;
+
+        egui::Window::new("Welcome to Pen Steer")
+            .open(&mut self.show_setup)
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, Vec2::ZERO)
+            .show(ctx, |ui| {
+                ui.label("Pick where pen input comes from, and what kind of virtual controller to create.");
+                ui.label("These can be changed later from the control panel.");
+
+                ui.separator();
+                select_source(ui, "setup_source", "Input Source", &mut state.config.source);
+                select_device(ui, "setup_device", &mut state.config.device);
+
+                ui.separator();
+                ui.horizontal(|ui| {
+                    start = ui.button("Start").clicked();
+
+                    if ui.button("About").clicked() {
+                        self.show_about = true;
+                    }
+                });
+            });
+
+        if start {
+            state.reset_source = true;
+            state.reset_device = true;
+            self.dirty_source_config = false;
+            self.dirty_device_config = false;
+            self.show_setup = false;
+        }
+    }
+
+    /// Reset the source or device, first asking for confirmation if that would
+    /// replace a working one with a different kind.
+    fn request_reset(&mut self, state: &mut State, target: ResetTarget) {
+        let switching_away = match target {
+            ResetTarget::Source => {
+                state.active_source != config::Source::None
+                    && state.config.source != state.active_source
+            }
+            ResetTarget::Device => {
+                state.active_device != config::Device::None
+                    && state.config.device != state.active_device
+            }
+        };
+
+        if switching_away {
+            self.pending_reset = Some(target);
+        } else {
+            self.reset(state, target);
+        }
+    }
+
+    fn reset(&mut self, state: &mut State, target: ResetTarget) {
+        match target {
+            ResetTarget::Source => {
+                state.reset_source = true;
+                self.dirty_source_config = false;
+            }
+            ResetTarget::Device => {
+                state.reset_device = true;
+                self.dirty_device_config = false;
             }
         }
+    }
 
-        ui.separator();
-        ui.heading("Mapping");
-        ui.style_mut().spacing.interact_size.x = 65.0;
-        let map = &mut config.mapping;
-        ui.horizontal(|ui| {
-            ui.label("Input:");
-            ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
-                ui.add(egui::DragValue::new(&mut map.min_in_x).speed(0.1));
-                ui.add(egui::DragValue::new(&mut map.min_in_y).speed(0.1));
-                ui.add(egui::DragValue::new(&mut map.max_in_x).speed(0.1));
-                ui.add(egui::DragValue::new(&mut map.max_in_y).speed(0.1));
-            });
-        });
-        ui.horizontal(|ui| {
-            ui.label("Output:");
-            ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
-                ui.add(egui::DragValue::new(&mut map.min_out_x).speed(0.1));
-                ui.add(egui::DragValue::new(&mut map.min_out_y).speed(0.1));
-                ui.add(egui::DragValue::new(&mut map.max_out_x).speed(0.1));
-                ui.add(egui::DragValue::new(&mut map.max_out_y).speed(0.1));
-            });
-        });
-        egui::ComboBox::new("map-orient", "Orientation")
-            .selected_text(format!("{:?}", map.orientation))
-            .show_ui(ui, |ui| {
-                ui.selectable_value(&mut map.orientation, MapOrientation::None, "A0");
-                ui.selectable_value(&mut map.orientation, MapOrientation::A90, "A90");
-                ui.selectable_value(&mut map.orientation, MapOrientation::A180, "A180");
-                ui.selectable_value(&mut map.orientation, MapOrientation::A270, "A270");
-            });
-        ui.checkbox(&mut map.invert_x, "Invert X axis");
-        ui.checkbox(&mut map.invert_y, "Invert Y axis");
+    fn apply_game_preset(&mut self, config: &mut Config, preset: config::GamePreset) {
+        info!("Applying game preset: {preset}");
+        config.apply_game_preset(preset);
+        self.device_vendor_edit_buf.clear();
+        self.device_product_edit_buf.clear();
+        self.device_version_edit_buf.clear();
+        self.dirty_device_config = true;
+    }
 
-        ui.separator();
-        ui.heading("Output");
+    fn draw_preset_confirmation(&mut self, ctx: &Context, config: &mut Config) {
+        let Some(preset) = self.pending_preset else {
+            return;
+        };
 
-        let old_device = config.device;
-        egui::ComboBox::new("device", "Output Device")
-            .selected_text(old_device.to_string())
-            .show_ui(ui, |ui| {
-                ui.selectable_value(&mut config.device, config::Device::None, "Null");
-                #[cfg(target_os = "linux")]
-                ui.selectable_value(&mut config.device, config::Device::UInput, "Linux uinput");
-                #[cfg(target_os = "windows")]
-                ui.selectable_value(&mut config.device, config::Device::VigemBus, "ViGEm Bus");
+        let mut confirmed = None;
+        let modal = egui::Modal::new(Id::new("confirm_preset")).show(ctx, |ui| {
+            ui.heading(format!("Apply {preset}?"));
+            ui.label(format!(
+                "Your own settings for the {} will be overwritten.",
+                config.game_preset_overwrites(preset).join(", ")
+            ));
+
+            ui.separator();
+            ui.horizontal(|ui| {
+                if ui.button("Apply").clicked() {
+                    confirmed = Some(true);
+                }
+
+                if ui.button("Cancel").clicked() {
+                    confirmed = Some(false);
+                }
             });
+        });
 
-        if config.device != old_device {
-            self.dirty_device_config = true;
+        if modal.should_close() && confirmed.is_none() {
+            confirmed = Some(false);
         }
 
-        match old_device {
-            config::Device::None => {
-                ui.colored_label(Color32::YELLOW, "No output available!");
+        if let Some(confirmed) = confirmed {
+            self.pending_preset = None;
+            if confirmed {
+                self.apply_game_preset(config, preset);
             }
-            #[cfg(target_os = "linux")]
-            config::Device::UInput => {
-                ui.heading("Virtual Controller: (via uinput)");
-                ui.horizontal(|ui| {
-                    ui.label("Name:");
-                    self.dirty_device_config |=
-                        ui.text_edit_singleline(&mut config.device_name).changed();
-                });
+        }
+    }
 
-                ui.horizontal(|ui| {
-                    ui.label("Vendor:");
-                    self.dirty_device_config |= edit_u16_hex(
-                        ui,
-                        &mut config.device_vendor,
-                        &mut self.device_vendor_edit_buf,
-                    );
-                });
-                ui.horizontal(|ui| {
-                    ui.label("Product:");
-                    self.dirty_device_config |= edit_u16_hex(
-                        ui,
-                        &mut config.device_product,
-                        &mut self.device_product_edit_buf,
-                    );
-                });
-                ui.horizontal(|ui| {
-                    ui.label("Version:");
-                    self.dirty_device_config |= edit_u16_hex(
-                        ui,
-                        &mut config.device_version,
-                        &mut self.device_version_edit_buf,
-                    );
-                });
+    fn draw_reset_confirmation(&mut self, ctx: &Context, state: &mut State) {
+        let Some(target) = self.pending_reset else {
+            return;
+        };
+
+        let (what, from, to) = match target {
+            ResetTarget::Source => (
+                "input source",
+                state.active_source.to_string(),
+                state.config.source.to_string(),
+            ),
+            ResetTarget::Device => (
+                "output device",
+                state.active_device.to_string(),
+                state.config.device.to_string(),
+            ),
+        };
+
+        let mut confirmed = None;
+        let modal = egui::Modal::new(Id::new("confirm_reset")).show(ctx, |ui| {
+            ui.heading(format!("Switch {what}?"));
+            ui.label(format!(
+                "The working {what} ({from}) will be closed and replaced with {to}. \
+                A game using it may lose its input."
+            ));
+
+            ui.separator();
+            ui.horizontal(|ui| {
+                if ui.button("Switch").clicked() {
+                    confirmed = Some(true);
+                }
+
+                if ui.button("Cancel").clicked() {
+                    confirmed = Some(false);
+                }
+            });
+        });
+
+        if modal.should_close() && confirmed.is_none() {
+            confirmed = Some(false);
+        }
+
+        match confirmed {
+            Some(true) => {
+                self.pending_reset = None;
+                self.reset(state, target);
             }
-            #[cfg(target_os = "windows")]
-            config::Device::VigemBus => {
-                ui.colored_label(Color32::YELLOW, "Work in progress...");
+            Some(false) => {
+                // Put back the selection which is still in use.
+                self.pending_reset = None;
+                match target {
+                    ResetTarget::Source => state.config.source = state.active_source,
+                    ResetTarget::Device => state.config.device = state.active_device,
+                }
             }
+            None => {}
         }
     }
 
@@ -652,28 +2577,118 @@ impl GuiApp {
     }
 }
 
-fn draw_steer_bar(angle: f32, config: &Config, ui: &mut Ui) -> Option<f32> {
-    let ui_rect = ui.min_rect();
+fn select_source(ui: &mut Ui, id: &str, label: &str, source: &mut config::Source) {
+    egui::ComboBox::new(id, label)
+        .selected_text(source.to_string())
+        .show_ui(ui, |ui| {
+            ui.selectable_value(source, config::Source::None, "Disabled");
+            ui.selectable_value(source, config::Source::Net, "Network (over UDP)");
+            #[cfg(target_os = "windows")]
+            ui.selectable_value(source, config::Source::Wintab, "Wacom Wintab (Windows)");
+            #[cfg(target_os = "linux")]
+            ui.selectable_value(source, config::Source::Evdev, "Evdev (Linux)");
+        });
+}
+
+fn select_pen_button(ui: &mut Ui, label: &str, button: &mut Option<u8>) -> egui::Response {
+    egui::ComboBox::new(label, label)
+        .selected_text(match button {
+            Some(bit) => format!("Bit {bit}"),
+            None => "None".to_string(),
+        })
+        .show_ui(ui, |ui| {
+            ui.selectable_value(button, None, "None");
+            for bit in 0..8 {
+                ui.selectable_value(button, Some(bit), format!("Bit {bit}"));
+            }
+        })
+        .response
+}
+
+fn select_device(ui: &mut Ui, id: &str, device: &mut config::Device) {
+    egui::ComboBox::new(id, "Output Device")
+        .selected_text(device.to_string())
+        .show_ui(ui, |ui| {
+            ui.selectable_value(device, config::Device::None, "Null");
+            #[cfg(target_os = "linux")]
+            ui.selectable_value(device, config::Device::UInput, "Linux uinput");
+            #[cfg(target_os = "linux")]
+            ui.selectable_value(device, config::Device::Keys, "Keyboard keys (uinput)");
+            #[cfg(target_os = "linux")]
+            ui.selectable_value(device, config::Device::Mouse, "Relative mouse (uinput)");
+            #[cfg(target_os = "windows")]
+            ui.selectable_value(device, config::Device::VigemBus, "ViGEm Bus");
+        });
+}
 
+/// Part of a bar between two offsets (in points) from its centre. Anything
+/// nonzero is kept at least a sliver wide so that small values stay visible.
+fn bar_span(ui_rect: Rect, from: f32, to: f32) -> Rect {
+    let min_width = (ui_rect.height() * 0.1).max(1.0);
     let centre = ui_rect.center().x;
-    let bound = ui_rect.width() * 0.5;
-    let range = config.half_range_rad();
-    let mut min = 0.0;
-    let mut max = (angle / range) * bound;
-    let mut colour = Color32::BLUE;
+    let mut min = from.min(to);
+    let mut max = from.max(to);
 
-    if min > max {
-        std::mem::swap(&mut min, &mut max);
-        colour = Color32::RED;
+    if max > min && max - min < min_width {
+        if to > from {
+            max = min + min_width;
+        } else {
+            min = max - min_width;
+        }
     }
 
-    let bar_rect = Rect {
+    Rect {
         min: Pos2::new(centre + min, ui_rect.min.y),
         max: Pos2::new(centre + max, ui_rect.max.y),
+    }
+}
+
+fn bar_corner_radius(rect: Rect, rounded: bool) -> CornerRadius {
+    if !rounded {
+        return CornerRadius::ZERO;
+    }
+
+    CornerRadius::same((rect.height() * 0.25).min(rect.width() * 0.5) as u8)
+}
+
+/// Edit a source's poll rate, where 0 follows the update frequency.
+fn edit_poll_rate(ui: &mut Ui, rate: &mut u32) {
+    ui.horizontal(|ui| {
+        ui.label("Poll rate:");
+        ui.add(
+            egui::DragValue::new(rate)
+                .range(0..=config::MAX_SOURCE_POLL_RATE)
+                .custom_formatter(|n, _| {
+                    if n == 0.0 {
+                        "Update rate".into()
+                    } else {
+                        format!("{n} Hz")
+                    }
+                }),
+        )
+        .on_hover_text(
+            "How often the source is read, to match how fast it samples. \
+            Faster than the update frequency, it is also read between updates, \
+            and each update uses the latest sample. 0 reads it once on every update.",
+        );
+    });
+}
+
+fn draw_steer_bar(angle: f32, config: &Config, rounded: bool, ui: &mut Ui) -> Option<f32> {
+    let ui_rect = ui.min_rect();
+
+    let bound = ui_rect.width() * 0.5;
+    let range = config.half_range_rad();
+    let colour = if angle < 0.0 {
+        Color32::RED
+    } else {
+        Color32::BLUE
     };
 
+    let bar_rect = bar_span(ui_rect, 0.0, (angle / range) * bound);
+
     ui.painter_at(ui_rect)
-        .rect_filled(bar_rect, CornerRadius::ZERO, colour);
+        .rect_filled(bar_rect, bar_corner_radius(bar_rect, rounded), colour);
 
     // allow user to click on the bar to set the angle
     if let Some(pos) = ui
@@ -686,58 +2701,226 @@ fn draw_steer_bar(angle: f32, config: &Config, ui: &mut Ui) -> Option<f32> {
         if pos.x >= left && pos.x <= right && ui.input(|i| i.pointer.any_down()) {
             return Some(math::remap(pos.x, left, right, -range, range));
         }
+
+        // or scroll over it for fine adjustments
+        let nudge = take_scroll_nudge(ui);
+        if nudge != 0.0 {
+            return Some(math::clamp_symmetric(range, angle + nudge));
+        }
     }
 
     None
 }
 
-fn draw_ff_bar(torque: f32, max: f32, ui: &mut Ui) {
+/// Consume any scrolling this frame and convert it into a small angle nudge (in radians).
+fn take_scroll_nudge(ui: &Ui) -> f32 {
+    const DEGREES_PER_NOTCH: f32 = 0.25;
+    const POINTS_PER_NOTCH: f32 = 50.0;
+
+    let scroll = ui.input_mut(|i| std::mem::take(&mut i.smooth_scroll_delta));
+    let notches = (scroll.x + scroll.y) / POINTS_PER_NOTCH;
+
+    (notches * DEGREES_PER_NOTCH).to_radians()
+}
+
+/// Plain-text summary of the open source and device, to attach to bug reports.
+fn device_report(state: &State) -> String {
+    let source = state
+        .source
+        .as_ref()
+        .map_or_else(|| "Not open.".to_string(), |source| source.describe());
+    let device = state
+        .device
+        .as_ref()
+        .map_or_else(|| "Not open.".to_string(), |device| device.describe());
+
+    format!(
+        "pen-steer v{}\n\n\
+        Source ({:?}):\n{source}\n\n\
+        Device ({:?}):\n{device}\n",
+        env!("CARGO_PKG_VERSION"),
+        state.config.source,
+        state.config.device,
+    )
+}
+
+/// Angle in degrees, or as a percentage of the way to lock.
+fn format_angle(angle: f32, half_range: f32, as_lock_percent: bool, decimals: usize) -> String {
+    if as_lock_percent {
+        format!("{:.*}%", decimals, 100.0 * angle / half_range)
+    } else {
+        format!("{:.*}°", decimals, angle.to_degrees())
+    }
+}
+
+/// Torque in Nm, or as a percentage of the max feedback torque.
+fn format_torque(torque: f32, max_torque: f32, as_percent: bool) -> String {
+    if as_percent && max_torque != 0.0 {
+        format!("{:.0}%", 100.0 * torque / max_torque.abs())
+    } else {
+        format!("{torque:.2} Nm")
+    }
+}
+
+/// How long a source or device may go quiet before it is shown as unhealthy.
+const HEALTH_TIMEOUT: Duration = Duration::from_secs(2);
+
+fn is_recent(instant: Option<Instant>) -> bool {
+    instant.is_some_and(|t| t.elapsed() < HEALTH_TIMEOUT)
+}
+
+fn source_health(state: &State) -> (bool, &'static str) {
+    if state.source.is_none() {
+        (false, "No source is open. Pick one and press Reset Source.")
+    } else if !is_recent(state.last_source_sample) {
+        (
+            false,
+            "No samples received recently. Check that the pen is in range, \
+            or press Reset Source.",
+        )
+    } else {
+        (true, "Receiving samples.")
+    }
+}
+
+fn device_health(state: &State) -> (bool, &'static str) {
+    if state.device.is_none() || state.device_fallback {
+        (false, "No device is open. Pick one and press Reset Device.")
+    } else if state
+        .device
+        .as_ref()
+        .is_some_and(|device| !device.connected())
+    {
+        (false, "Lost the connection to the driver. Reconnecting...")
+    } else if !is_recent(state.last_device_apply) {
+        (
+            false,
+            "The device has stopped accepting updates. Try Reset Device.",
+        )
+    } else {
+        (true, "Sending updates.")
+    }
+}
+
+fn draw_health_dot(ui: &mut Ui, label: &str, healthy: bool) -> egui::Response {
+    ui.horizontal(|ui| {
+        let (rect, _) = ui.allocate_exact_size(Vec2::splat(10.0), Sense::hover());
+        let color = if healthy {
+            Color32::GREEN
+        } else {
+            Color32::RED
+        };
+        ui.painter().circle_filled(rect.center(), 4.0, color);
+        ui.label(label);
+    })
+    .response
+}
+
+fn draw_ff_bar(torque: f32, band: Option<(f32, f32)>, max: f32, rounded: bool, ui: &mut Ui) {
+    const BAND_COLOUR: Color32 = Color32::from_rgba_premultiplied(0x29, 0x0A, 0x0A, 0x40);
+
     let ui_rect = ui.min_rect();
 
-    let centre = ui_rect.center().x;
     let bound = ui_rect.width() * 0.5;
-    let mut min = 0.0;
-    let mut max = (torque / max) * bound;
-    let colour = Color32::BROWN;
+    let span = |from: f32, to: f32| bar_span(ui_rect, (from / max) * bound, (to / max) * bound);
+
+    let painter = ui.painter_at(ui_rect);
 
-    if min > max {
-        std::mem::swap(&mut min, &mut max);
+    if let Some((low, high)) = band {
+        let band_rect = span(low, high);
+        let radius = bar_corner_radius(band_rect, rounded);
+        painter.rect_filled(band_rect, radius, BAND_COLOUR);
     }
 
-    let bar_rect = Rect {
-        min: Pos2::new(centre + min, ui_rect.min.y),
-        max: Pos2::new(centre + max, ui_rect.max.y),
+    let bar_rect = span(0.0, torque);
+    let radius = bar_corner_radius(bar_rect, rounded);
+    painter.rect_filled(bar_rect, radius, Color32::BROWN);
+}
+
+/// Use the image file at `path` if there is one and it loads, otherwise the bundled `fallback`.
+fn wheel_image_source<'a>(
+    ui: &Ui,
+    path: Option<&str>,
+    fallback: egui::ImageSource<'a>,
+) -> egui::ImageSource<'a> {
+    let Some(path) = path else {
+        return fallback;
     };
 
-    ui.painter_at(ui_rect)
-        .rect_filled(bar_rect, CornerRadius::ZERO, colour);
+    // Failures are cached by the loader, so this doesn't hit the disk every frame.
+    let uri = format!("file://{path}");
+    match ui.ctx().try_load_image(&uri, egui::SizeHint::default()) {
+        Ok(_) => egui::ImageSource::Uri(uri.into()),
+        Err(_) => fallback,
+    }
+}
+
+/// Square area the steering wheel is drawn in.
+fn wheel_rect(available_rect: Rect) -> Rect {
+    let mut rect = available_rect.scale_from_center(0.95);
+
+    // keep the rect a square
+    if rect.width() > rect.height() {
+        let extra = rect.width() - rect.height();
+        rect = rect.shrink2(Vec2::X * extra * 0.5);
+    } else if rect.height() > rect.width() {
+        let extra = rect.height() - rect.width();
+        rect = rect.shrink2(Vec2::Y * extra * 0.5);
+    }
+
+    rect
+}
+
+/// Arc around the rim of the wheel, sweeping from its top in the direction the
+/// feedback torque turns it, further the stronger the torque.
+fn draw_feedback_overlay(config: &Config, wheel: &Wheel, display_sign: f32, ui: &mut Ui) {
+    const COLOUR: Color32 = Color32::YELLOW;
+    /// Angle swept at full torque.
+    const SWEEP: f32 = FRAC_PI_2;
+    const SEGMENTS: usize = 32;
+
+    if config.max_torque == 0.0 || wheel.feedback_torque == 0.0 {
+        return;
+    }
+
+    let available_rect = ui.ctx().available_rect();
+    let rect = wheel_rect(available_rect);
+    let radius = rect.width() * 0.5;
+    let level = (wheel.feedback_torque / config.max_torque).clamp(-1.0, 1.0);
+
+    let points: Vec<Pos2> = (0..=SEGMENTS)
+        .map(|i| {
+            let angle = display_sign * (wheel.angle + level * SWEEP * i as f32 / SEGMENTS as f32);
+            rect.center() + radius * Vec2::new(angle.sin(), -angle.cos())
+        })
+        .collect();
+    let tip = points[SEGMENTS];
+
+    let painter = ui.painter_at(available_rect);
+    painter.add(egui::Shape::line(points, Stroke::new(4.0, COLOUR)));
+    painter.circle_filled(tip, 6.0, COLOUR);
 }
 
 fn draw_steering_wheel(
     config: &Config,
     wheel: &Wheel,
     base_radius_selection: Option<f32>,
+    image_rotation: f32,
     pen: Option<Pen>,
+    raw_pen: Option<&RawPen>,
     ui: &mut Ui,
 ) -> Option<Pen> {
     const BASE_RADIUS_HIGHLIGHT_COLOUR: Color32 =
         Color32::from_rgba_premultiplied(0xAD, 0xD8, 0xE6, 0x80);
     const PEN_COLOUR: Color32 = Color32::CYAN;
+    const STALE_PEN_COLOUR: Color32 = Color32::GRAY;
     const HORN_COLOUR: Color32 = Color32::PURPLE;
+    const RAW_PEN_COLOUR: Color32 = Color32::ORANGE;
     const PEN_SIZE: f32 = 12.0;
     const HORN_PRESS_SCALE: f32 = 0.9;
 
     let available_rect = ui.ctx().available_rect();
-    let mut rect = available_rect.scale_from_center(0.95);
-
-    // keep the rect a square
-    if rect.width() > rect.height() {
-        let extra = rect.width() - rect.height();
-        rect = rect.shrink2(Vec2::X * extra * 0.5);
-    } else if rect.height() > rect.width() {
-        let extra = rect.height() - rect.width();
-        rect = rect.shrink2(Vec2::Y * extra * 0.5);
-    }
+    let rect = wheel_rect(available_rect);
 
     let left = rect.left();
     let right = rect.right();
@@ -750,14 +2933,25 @@ fn draw_steering_wheel(
         config.horn_radius
     });
 
-    egui::Image::new(egui::include_image!("../resources/base.svg"))
+    let base_image = wheel_image_source(
+        ui,
+        config.wheel_base_image.as_deref(),
+        egui::include_image!("../resources/base.svg"),
+    );
+    let inner_image = wheel_image_source(
+        ui,
+        config.wheel_inner_image.as_deref(),
+        egui::include_image!("../resources/inner.svg"),
+    );
+
+    egui::Image::new(base_image)
         .alt_text("Base Image")
-        .rotate(wheel.angle, Vec2::splat(0.5))
+        .rotate(image_rotation, Vec2::splat(0.5))
         .paint_at(ui, rect);
 
-    egui::Image::new(egui::include_image!("../resources/inner.svg"))
+    egui::Image::new(inner_image)
         .alt_text("Inner Image")
-        .rotate(wheel.angle, Vec2::splat(0.5))
+        .rotate(image_rotation, Vec2::splat(0.5))
         .tint(if wheel.honking {
             HORN_COLOUR
         } else {
@@ -775,16 +2969,57 @@ fn draw_steering_wheel(
         );
     }
 
+    // Warn of an impending honk as the pen nears the centre.
+    if let Some(pen) = &pen
+        && config.horn_enabled
+        && config.steering_model == config::SteeringModel::Radial
+        && !wheel.dragging
+        && !wheel.honking
+        && wheel.horn_cooldown <= 0.0
+        && config.horn_radius > 0.0
+    {
+        let centre_dist = math::dist_sq(pen.x, pen.y).sqrt();
+        let closeness =
+            1.0 - ((centre_dist - config.horn_radius) / config.horn_radius).clamp(0.0, 1.0);
+
+        if closeness > 0.0 {
+            painter.circle_stroke(
+                rect.center(),
+                config.horn_radius * rect.width() * 0.5,
+                Stroke::new(1.0 + closeness * 3.0, HORN_COLOUR.gamma_multiply(closeness)),
+            );
+        }
+    }
+
+    if let Some(raw_pen) = raw_pen {
+        let pos = Pos2 {
+            x: math::remap(raw_pen.x, -1.0, 1.0, right, left),
+            y: math::remap(raw_pen.y, -1.0, 1.0, top, bottom),
+        };
+
+        let stroke = Stroke::new(2.0, RAW_PEN_COLOUR);
+        painter.line_segment([pos - Vec2::X * PEN_SIZE, pos + Vec2::X * PEN_SIZE], stroke);
+        painter.line_segment([pos - Vec2::Y * PEN_SIZE, pos + Vec2::Y * PEN_SIZE], stroke);
+    }
+
     if let Some(pen) = pen {
+        let colour = if pen.stale {
+            STALE_PEN_COLOUR
+        } else {
+            PEN_COLOUR
+        };
+
         let pos = Pos2 {
             x: math::remap(pen.x, -1.0, 1.0, right, left),
             y: math::remap(pen.y, -1.0, 1.0, top, bottom),
         };
 
         if pen.pressure > config.pressure_threshold {
-            painter.circle_filled(pos, PEN_SIZE, PEN_COLOUR);
+            painter.circle_filled(pos, PEN_SIZE, colour);
         } else {
-            painter.circle_stroke(pos, PEN_SIZE, Stroke::new(2.0, PEN_COLOUR));
+            // fade out the further the pen is hovering
+            let fade = 1.0 - pen.distance.unwrap_or(0.0) * 0.8;
+            painter.circle_stroke(pos, PEN_SIZE, Stroke::new(2.0, colour.gamma_multiply(fade)));
         }
     }
 
@@ -792,18 +3027,18 @@ fn draw_steering_wheel(
     if let Some(pos) = ui
         .interact(rect, Id::new("wheel_box"), Sense::click_and_drag())
         .hover_pos()
+        && rect.contains(pos)
+        && ui.input(|i| i.pointer.primary_down())
     {
-        if rect.contains(pos) && ui.input(|i| i.pointer.primary_down()) {
-            let x = math::remap(pos.x, right, left, -1.0, 1.0);
-            let y = math::remap(pos.y, top, bottom, -1.0, 1.0);
-
-            return Some(Pen {
-                x,
-                y,
-                pressure: u32::MAX,
-                ..Default::default()
-            });
-        }
+        let x = math::remap(pos.x, right, left, -1.0, 1.0);
+        let y = math::remap(pos.y, top, bottom, -1.0, 1.0);
+
+        return Some(Pen {
+            x,
+            y,
+            pressure: u32::MAX,
+            ..Default::default()
+        });
     }
 
     None
@@ -823,10 +3058,10 @@ fn draw_about(ctx: &Context, show_about: &mut bool) {
             ui.allocate_space(ui.available_size());
         });
 
-    if let Some(response) = response {
-        if response.response.clicked() {
-            *show_about = false;
-        }
+    if let Some(response) = response
+        && response.response.clicked()
+    {
+        *show_about = false;
     }
 
     egui::Window::new("About pen-steer")
@@ -856,10 +3091,19 @@ fn draw_about(ctx: &Context, show_about: &mut bool) {
     }
 }
 
-pub fn gui(state: Arc<Mutex<State>>, quit_flag: Arc<AtomicBool>) -> eframe::Result {
+/// Run the GUI until the window is closed or the quit flag is set.
+///
+/// With `hide_on_close`, closing the window only hides it, and `State::hide_gui`
+/// hides or shows it again; this only returns once the quit flag is set. The
+/// event loop can't be created twice, so this is how the window is reopened.
+pub fn gui(
+    state: Arc<Mutex<State>>,
+    quit_flag: Arc<AtomicBool>,
+    hide_on_close: bool,
+) -> eframe::Result {
     let options = eframe::NativeOptions {
         viewport: ViewportBuilder {
-            title: Some("Pen Steer".into()),
+            title: Some(WINDOW_TITLE.into()),
             app_id: Some("pen-steer".into()),
             inner_size: Some(Vec2::new(800.0, 600.0)),
             min_inner_size: Some(Vec2::new(365.0, 0.0)),
@@ -870,14 +3114,20 @@ pub fn gui(state: Arc<Mutex<State>>, quit_flag: Arc<AtomicBool>) -> eframe::Resu
         ..Default::default()
     };
 
-    eframe::run_native(
+    let app_state = state.clone();
+    let result = eframe::run_native(
         "pen-steer",
         options,
         Box::new(|cc| {
             egui_extras::install_image_loaders(&cc.egui_ctx);
-            Ok(Box::new(GuiApp::new(state, quit_flag)))
+            Ok(Box::new(GuiApp::new(app_state, quit_flag, hide_on_close)))
         }),
-    )
+    );
+
+    // Without a window, there is no focus to lose.
+    state.lock().unwrap().gui_unfocused = false;
+
+    result
 }
 
 fn edit_u16_hex(ui: &mut Ui, value: &mut u16, buf: &mut String) -> bool {
@@ -895,11 +3145,11 @@ fn edit_u16_hex(ui: &mut Ui, value: &mut u16, buf: &mut String) -> bool {
     if out.response.lost_focus() || out.response.clicked_elsewhere() {
         let stripped = buf.trim().trim_start_matches("0x");
 
-        if let Ok(new_value) = u16::from_str_radix(stripped, 16) {
-            if new_value != *value {
-                *value = new_value;
-                dirty = true;
-            }
+        if let Ok(new_value) = u16::from_str_radix(stripped, 16)
+            && new_value != *value
+        {
+            *value = new_value;
+            dirty = true;
         }
 
         buf.clear();
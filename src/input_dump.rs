@@ -0,0 +1,63 @@
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use anyhow::{Context, Result};
+
+use crate::pen::RawPen;
+
+/// Bytes held in memory before they are written out, so that the disk is not
+/// touched for every sample.
+const BUFFER_SIZE: usize = 64 * 1024;
+/// Longest time samples may sit in the buffer before being written out.
+const FLUSH_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Human-readable CSV log of every sample from the source, for analysis.
+pub struct InputDump {
+    writer: BufWriter<File>,
+    path: PathBuf,
+    start: Instant,
+    last_flush: Instant,
+}
+
+impl InputDump {
+    pub fn create(path: &Path) -> Result<Self> {
+        let file = File::create(path).context("Could not create input dump file.")?;
+        let mut writer = BufWriter::with_capacity(BUFFER_SIZE, file);
+        writeln!(&mut writer, "time_s,x,y,pressure,buttons")?;
+
+        let now = Instant::now();
+        Ok(Self {
+            writer,
+            path: path.to_owned(),
+            start: now,
+            last_flush: now,
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn write(&mut self, pen: &RawPen) -> Result<()> {
+        writeln!(
+            &mut self.writer,
+            "{:.6},{},{},{},{}",
+            self.start.elapsed().as_secs_f64(),
+            pen.x,
+            pen.y,
+            pen.pressure,
+            pen.buttons
+        )?;
+
+        if self.last_flush.elapsed() >= FLUSH_INTERVAL {
+            self.writer.flush()?;
+            self.last_flush = Instant::now();
+        }
+
+        Ok(())
+    }
+}
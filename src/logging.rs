@@ -0,0 +1,83 @@
+use std::{
+    collections::VecDeque,
+    sync::{Mutex, OnceLock},
+};
+
+use log::{LevelFilter, Log, Metadata, Record};
+
+/// How many recent lines are kept for the log view.
+const RECENT_CAPACITY: usize = 500;
+
+static LOGGER: OnceLock<Logger> = OnceLock::new();
+
+/// Wraps `env_logger` so the level can be changed at runtime, and keeps recent
+/// lines around so they can be shown in the GUI.
+struct Logger {
+    inner: env_logger::Logger,
+    recent: Mutex<VecDeque<String>>,
+}
+
+impl Log for Logger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= log::max_level() && self.inner.enabled(metadata)
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) || !self.inner.matches(record) {
+            return;
+        }
+
+        self.inner.log(record);
+
+        let mut recent = self.recent.lock().unwrap();
+        if recent.len() >= RECENT_CAPACITY {
+            recent.pop_front();
+        }
+        recent.push_back(format!(
+            "[{} {}] {}",
+            record.level(),
+            record.target(),
+            record.args()
+        ));
+    }
+
+    fn flush(&self) {
+        self.inner.flush();
+    }
+}
+
+/// Set up logging. The initial level comes from `RUST_LOG`, defaulting to Info.
+pub fn init() {
+    let inner = env_logger::Builder::default()
+        .filter_level(LevelFilter::Info)
+        .parse_default_env()
+        .filter_module("eframe", LevelFilter::Warn)
+        .filter_module("calloop", LevelFilter::Warn)
+        .build();
+    let initial_level = inner.filter();
+
+    let logger = LOGGER.get_or_init(|| Logger {
+        inner,
+        recent: Mutex::new(VecDeque::with_capacity(RECENT_CAPACITY)),
+    });
+
+    if log::set_logger(logger).is_ok() {
+        log::set_max_level(initial_level);
+    }
+}
+
+pub fn level() -> LevelFilter {
+    log::max_level()
+}
+
+pub fn set_level(level: LevelFilter) {
+    log::set_max_level(level);
+}
+
+/// Copy of the most recently logged lines, oldest first.
+pub fn recent_lines() -> Vec<String> {
+    LOGGER
+        .get()
+        .map(|logger| logger.recent.lock().unwrap().iter().cloned().collect())
+        .unwrap_or_default()
+}
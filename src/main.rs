@@ -4,9 +4,12 @@ mod config;
 mod controller;
 mod device;
 mod gui;
+mod input_dump;
+mod logging;
 mod mapping;
 mod math;
 mod pen;
+mod physics_capture;
 mod save;
 mod save_path;
 mod source;
@@ -17,44 +20,91 @@ mod wheel;
 use std::{
     env::args,
     fs::create_dir_all,
+    io::stdin,
+    path::PathBuf,
     sync::{
         Arc, Mutex,
         atomic::{AtomicBool, Ordering},
+        mpsc,
     },
+    time::{Duration, Instant},
 };
 
 use anyhow::{Result, bail};
 
-use log::{LevelFilter, error, info};
+use log::{error, info, warn};
 
-use crate::{save_path::save_dir, state::State};
+use crate::{
+    input_dump::InputDump,
+    pen::RawPen,
+    save_path::{override_save_path, save_dir},
+    source::channel::ChannelSource,
+    state::State,
+};
 
 const VERSION: &str = env!("CARGO_PKG_VERSION");
 
 fn main() -> Result<()> {
-    init_logging();
+    logging::init();
     info!("pen-steer v{VERSION}");
 
+    if args().any(|arg| arg.trim() == "--bench") {
+        return bench();
+    }
+
+    if let Some(path) = path_arg("--config") {
+        info!("Using configuration file: {}", path.display());
+        override_save_path(path);
+    }
+
+    if args().any(|arg| arg.trim() == "--mapping-table") {
+        let config = State::create().config;
+        info!("Mapping of the configured input area (size in mm unknown without a source):");
+        print!("{}", config.mapping.debug_table(5, None));
+        return Ok(());
+    }
+
     if let Err(err) = create_dir_all(save_dir()) {
         error!("Could not create configuration directory: {err}");
     }
 
     let state = Arc::new(Mutex::new(State::create()));
+
+    if let Some(path) = path_arg("--dump-input") {
+        match InputDump::create(&path) {
+            Ok(dump) => {
+                info!("Dumping input samples to: {}", path.display());
+                state.lock().unwrap().input_dump = Some(dump);
+            }
+            Err(err) => error!("Could not start input dump: {err}"),
+        }
+    }
+
     let quit_flag = Arc::new(AtomicBool::new(false));
 
     set_handler(quit_flag.clone());
 
     let cli_mode = args().any(|arg| arg.trim() == "--headless");
     if cli_mode {
-        controller::controller(state, quit_flag);
-        return Ok(());
+        let unconfigured = {
+            let config = &state.lock().unwrap().config;
+            config.source == config::Source::None || config.device == config::Device::None
+        };
+
+        if unconfigured {
+            warn!(
+                "No source or device configured; pick them in the GUI or the configuration file."
+            );
+        }
     }
 
     let state_clone = state.clone();
     let quit_flag_clone = quit_flag.clone();
     let thread = std::thread::spawn(move || controller::controller(state_clone, quit_flag_clone));
 
-    if let Err(err) = gui::gui(state, quit_flag.clone()) {
+    if cli_mode {
+        headless(state, &quit_flag);
+    } else if let Err(err) = gui::gui(state, quit_flag.clone(), false) {
         bail!("GUI error: {err}");
     }
 
@@ -64,6 +114,128 @@ fn main() -> Result<()> {
     Ok(())
 }
 
+/// Keep running without a window, taking commands from standard input to open
+/// or close the GUI against the running controller, or to stop.
+///
+/// These commands stand in for a control socket: only whoever started the
+/// process can send them.
+fn headless(state: Arc<Mutex<State>>, quit_flag: &Arc<AtomicBool>) {
+    const WAKE_INTERVAL: Duration = Duration::from_millis(100);
+
+    info!("Running headless. Enter \"gui\" to open the window, \"close\" or \"quit\".");
+
+    // The GUI has to run on the main thread, so only the request to open it is
+    // passed back; the rest can be handled straight away. Once open, the window
+    // is only ever hidden, as its event loop can't be created again.
+    let (open_sender, open_receiver) = mpsc::channel();
+    {
+        let state = state.clone();
+        let quit_flag = quit_flag.clone();
+        std::thread::spawn(move || {
+            for line in stdin().lines() {
+                let Ok(line) = line else {
+                    break;
+                };
+
+                match line.trim() {
+                    "" => {}
+                    "gui" => {
+                        state.lock().unwrap().hide_gui = false;
+                        if open_sender.send(()).is_err() {
+                            break;
+                        }
+                    }
+                    "close" => state.lock().unwrap().hide_gui = true,
+                    "quit" => quit_flag.store(true, Ordering::Release),
+                    other => warn!("Unknown command \"{other}\"."),
+                }
+            }
+        });
+    }
+
+    while !quit_flag.load(Ordering::Acquire) {
+        if open_receiver.recv_timeout(WAKE_INTERVAL).is_err() {
+            continue;
+        }
+
+        info!("Opening the GUI.");
+        state.lock().unwrap().hide_gui = false;
+        match gui::gui(state.clone(), quit_flag.clone(), true) {
+            Ok(()) => break,
+            // The window couldn't be opened, e.g. without a display. Requests
+            // made in the meantime are dropped; ask again to retry.
+            Err(err) => {
+                error!("GUI error: {err}");
+                while open_receiver.try_recv().is_ok() {}
+            }
+        }
+    }
+}
+
+/// Run the controller as fast as possible without any real source or device,
+/// and report how long each tick takes.
+fn bench() -> Result<()> {
+    const ITERATIONS: usize = 1_000_000;
+    const DRAG_PERIOD: usize = 1000;
+
+    info!("Benchmarking {ITERATIONS} ticks...");
+
+    let (source, pen_sender) = ChannelSource::new();
+    let mut state = State {
+        source: Some(Box::new(source)),
+        reset_source: false,
+        ..Default::default()
+    };
+    let mut tick_times = Vec::with_capacity(ITERATIONS);
+
+    let start = Instant::now();
+    for i in 0..ITERATIONS {
+        // Alternate between dragging the wheel around and letting it spin freely.
+        let dragging = (i / DRAG_PERIOD).is_multiple_of(2);
+        let theta = i as f32 * 0.01;
+        pen_sender.send(RawPen {
+            x: theta.sin(),
+            y: theta.cos(),
+            pressure: if dragging { u32::MAX } else { 0 },
+            ..Default::default()
+        })?;
+
+        let tick_start = Instant::now();
+        controller::update(&mut state)?;
+        tick_times.push(tick_start.elapsed());
+    }
+    let total = start.elapsed();
+
+    tick_times.sort_unstable();
+    let percentile = |p: f64| tick_times[((tick_times.len() - 1) as f64 * p) as usize];
+    let mean = tick_times.iter().sum::<Duration>() / ITERATIONS as u32;
+
+    info!(
+        "{:.0} ticks/s\n\tmean: {mean:?}\n\tp50: {:?}\n\tp90: {:?}\n\tp99: {:?}\n\tmax: {:?}",
+        ITERATIONS as f64 / total.as_secs_f64(),
+        percentile(0.5),
+        percentile(0.9),
+        percentile(0.99),
+        percentile(1.0),
+    );
+
+    Ok(())
+}
+
+/// Path given after the `flag` argument, e.g. `--config <path>`, if any.
+fn path_arg(flag: &str) -> Option<PathBuf> {
+    let mut args = args().skip_while(|arg| arg.trim() != flag);
+    args.next()?;
+
+    match args.next() {
+        Some(path) => Some(PathBuf::from(path)),
+        None => {
+            error!("Missing path after {flag}.");
+            None
+        }
+    }
+}
+
 fn set_handler(quit_flag: Arc<AtomicBool>) {
     if let Err(err) = ctrlc::set_handler(move || {
         quit_flag.store(true, Ordering::Release);
@@ -71,12 +243,3 @@ fn set_handler(quit_flag: Arc<AtomicBool>) {
         error!("Could not set signal handler: {err}");
     }
 }
-
-fn init_logging() {
-    env_logger::Builder::default()
-        .filter_level(LevelFilter::Info)
-        .parse_default_env()
-        .filter_module("eframe", LevelFilter::Warn)
-        .filter_module("calloop", LevelFilter::Warn)
-        .init();
-}
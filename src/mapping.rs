@@ -1,5 +1,14 @@
 use crate::pen::{Pen, RawPen};
 
+/// Named source positions, useful for showing where a mapping sends them.
+pub const LANDMARKS: [(&str, (f32, f32)); 5] = [
+    ("Top left", (-1.0, -1.0)),
+    ("Top right", (1.0, -1.0)),
+    ("Bottom right", (1.0, 1.0)),
+    ("Bottom left", (-1.0, 1.0)),
+    ("Centre", (0.0, 0.0)),
+];
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum MapOrientation {
     None,
@@ -8,8 +17,40 @@ pub enum MapOrientation {
     A270,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Handedness {
+    Right,
+    /// Mirrors the X axis on top of the other mapping settings.
+    Left,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MapMode {
+    /// Input area is an axis-aligned rectangle.
+    Rect,
+    /// Input area is an arbitrary quadrilateral given by its four corners.
+    Quad,
+    /// Input area is a rectangle measured in millimetres on the tablet.
+    Physical,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClampShape {
+    /// Each axis is clamped to ±1 on its own.
+    Square,
+    /// The output is clamped to the unit circle, so diagonals reach as far as the axes.
+    Circle,
+}
+
 #[derive(Debug, Clone)]
 pub struct Mapping {
+    pub mode: MapMode,
+    /// Input corners used in `Quad` mode, in the order:
+    /// (min X, min Y), (max X, min Y), (max X, max Y), (min X, max Y).
+    pub corners: [(f32, f32); 4],
+    /// Input area used in `Physical` mode, as (min X, min Y, max X, max Y) in
+    /// millimetres from the centre of the tablet.
+    pub area_mm: (f32, f32, f32, f32),
     pub min_in_x: f32,
     pub min_in_y: f32,
     pub max_in_x: f32,
@@ -18,14 +59,25 @@ pub struct Mapping {
     pub min_out_y: f32,
     pub max_out_x: f32,
     pub max_out_y: f32,
+    pub clamp_shape: ClampShape,
     pub orientation: MapOrientation,
     pub invert_x: bool,
     pub invert_y: bool,
+    pub handedness: Handedness,
+    /// Raw pressure at and below which the pen reads as not pressing at all.
+    pub pressure_min: u32,
+    /// Highest raw pressure the source reports.
+    pub pressure_max: u32,
+    /// Whether the source reports lower pressure for pressing harder.
+    pub pressure_invert: bool,
 }
 
 impl Default for Mapping {
     fn default() -> Self {
         Self {
+            mode: MapMode::Rect,
+            corners: [(-1.0, -1.0), (1.0, -1.0), (1.0, 1.0), (-1.0, 1.0)],
+            area_mm: (-50.0, -50.0, 50.0, 50.0),
             min_in_x: -1.0,
             min_in_y: -1.0,
             max_in_x: 1.0,
@@ -34,19 +86,48 @@ impl Default for Mapping {
             min_out_y: -1.0,
             max_out_x: 1.0,
             max_out_y: 1.0,
+            clamp_shape: ClampShape::Square,
             orientation: MapOrientation::None,
             invert_x: false,
             invert_y: false,
+            handedness: Handedness::Right,
+            pressure_min: 0,
+            pressure_max: 8191,
+            pressure_invert: false,
         }
     }
 }
 
 impl Mapping {
-    pub fn transform(&self, mut x: f32, mut y: f32) -> (f32, f32) {
-        x = inv_lerp(x, self.min_in_x, self.max_in_x).clamp(0.0, 1.0);
-        y = inv_lerp(y, self.min_in_y, self.max_in_y).clamp(0.0, 1.0);
+    /// Map a normalised source position to output coordinates. `physical_size`
+    /// is the tablet's size in millimetres, needed for the `Physical` mode.
+    pub fn transform(
+        &self,
+        mut x: f32,
+        mut y: f32,
+        physical_size: Option<(f32, f32)>,
+    ) -> (f32, f32) {
+        let rect = |x, y| {
+            (
+                inv_lerp(x, self.min_in_x, self.max_in_x),
+                inv_lerp(y, self.min_in_y, self.max_in_y),
+            )
+        };
+
+        (x, y) = match self.mode {
+            MapMode::Rect => rect(x, y),
+            // A quad squashed flat has no inside to map from.
+            MapMode::Quad => inv_bilinear((x, y), self.corners).unwrap_or_else(|| rect(x, y)),
+            MapMode::Physical => {
+                let (min_x, min_y, max_x, max_y) = self.physical_area(physical_size);
+                (inv_lerp(x, min_x, max_x), inv_lerp(y, min_y, max_y))
+            }
+        };
+
+        x = x.clamp(0.0, 1.0);
+        y = y.clamp(0.0, 1.0);
 
-        if self.invert_x {
+        if self.effective_invert_x() {
             x = 1.0 - x;
         }
 
@@ -54,8 +135,22 @@ impl Mapping {
             y = 1.0 - y;
         }
 
-        x = lerp(x, self.min_out_x, self.max_out_x).clamp(-1.0, 1.0);
-        y = lerp(y, self.min_out_y, self.max_out_y).clamp(-1.0, 1.0);
+        x = lerp(x, self.min_out_x, self.max_out_x);
+        y = lerp(y, self.min_out_y, self.max_out_y);
+
+        match self.clamp_shape {
+            ClampShape::Square => {
+                x = x.clamp(-1.0, 1.0);
+                y = y.clamp(-1.0, 1.0);
+            }
+            ClampShape::Circle => {
+                let length = x.hypot(y);
+                if length > 1.0 {
+                    x /= length;
+                    y /= length;
+                }
+            }
+        }
 
         match self.orientation {
             MapOrientation::None => (x, y),
@@ -65,13 +160,85 @@ impl Mapping {
         }
     }
 
-    pub fn pen(&self, raw: RawPen) -> Pen {
-        let (x, y) = self.transform(raw.x, raw.y);
+    /// The millimetre input area in normalised source units. Sources span -1.0
+    /// to 1.0 across the shorter side of the tablet, cropping the longer one.
+    /// Without a known size, the rectangle input area is used instead.
+    fn physical_area(&self, physical_size: Option<(f32, f32)>) -> (f32, f32, f32, f32) {
+        let Some((width, height)) = physical_size else {
+            return (self.min_in_x, self.min_in_y, self.max_in_x, self.max_in_y);
+        };
+
+        let mm_per_unit = 0.5 * width.min(height);
+        let (min_x, min_y, max_x, max_y) = self.area_mm;
+        (
+            min_x / mm_per_unit,
+            min_y / mm_per_unit,
+            max_x / mm_per_unit,
+            max_y / mm_per_unit,
+        )
+    }
+
+    /// Table of outputs for an evenly spaced grid of source positions, one per
+    /// line, so that a mapping's behaviour can be shared without hardware.
+    pub fn debug_table(&self, steps: usize, physical_size: Option<(f32, f32)>) -> String {
+        let mut table = String::new();
+        let step = 2.0 / (steps.max(2) - 1) as f32;
+
+        for row in 0..steps.max(2) {
+            for col in 0..steps.max(2) {
+                let (x, y) = (-1.0 + col as f32 * step, -1.0 + row as f32 * step);
+                let (out_x, out_y) = self.transform(x, y, physical_size);
+                table += &format!("({x:+.2}, {y:+.2}) -> ({out_x:+.3}, {out_y:+.3})\n");
+            }
+        }
+
+        table
+    }
+
+    /// Whether the X axis ends up inverted, taking handedness into account.
+    pub fn effective_invert_x(&self) -> bool {
+        self.invert_x != (self.handedness == Handedness::Left)
+    }
+
+    fn pressure_span(&self) -> (u32, u32) {
+        let min = self.pressure_min.min(self.pressure_max.saturating_sub(1));
+        (min, self.pressure_max)
+    }
+
+    /// Clamp raw pressure into the configured range and shift it so that the
+    /// minimum reads as zero. Raw pressure at or below the minimum is no contact,
+    /// even on an inverted source, so a lifted pen never reads as pressed.
+    fn pressure(&self, raw: u32) -> u32 {
+        let (min, max) = self.pressure_span();
+        if raw <= min {
+            return 0;
+        }
+
+        let raw = raw.min(max);
+        if self.pressure_invert {
+            max - raw
+        } else {
+            raw - min
+        }
+    }
+
+    /// Mapped pressure as a fraction of the configured range, from 0.0 to 1.0.
+    pub fn pressure_level(&self, pressure: u32) -> f32 {
+        let (min, max) = self.pressure_span();
+        (pressure as f32 / (max - min) as f32).clamp(0.0, 1.0)
+    }
+
+    pub fn pen(&self, raw: RawPen, physical_size: Option<(f32, f32)>) -> Pen {
+        let (x, y) = self.transform(raw.x, raw.y, physical_size);
         Pen {
             x,
             y,
-            pressure: raw.pressure,
+            pressure: self.pressure(raw.pressure),
             buttons: raw.buttons,
+            distance: raw.distance,
+            tilt: raw.tilt,
+            stale: raw.stale,
+            tool: raw.tool,
         }
     }
 }
@@ -83,3 +250,229 @@ fn lerp(t: f32, b1: f32, b2: f32) -> f32 {
 fn inv_lerp(t: f32, a1: f32, a2: f32) -> f32 {
     (t - a1) / (a2 - a1)
 }
+
+/// Find the (u, v) coordinates of `p` within the quad `[a, b, c, d]`, such that
+/// bilinearly interpolating the corners by (u, v) gives back `p`.
+///
+/// `None` if the quad is degenerate, e.g. with its corners in a line.
+fn inv_bilinear(p: (f32, f32), [a, b, c, d]: [(f32, f32); 4]) -> Option<(f32, f32)> {
+    /// Smallest area of quad which is mapped from.
+    const MIN_AREA: f32 = 1e-4;
+
+    fn cross(a: (f32, f32), b: (f32, f32)) -> f32 {
+        a.0 * b.1 - a.1 * b.0
+    }
+
+    let diagonals = cross((c.0 - a.0, c.1 - a.1), (d.0 - b.0, d.1 - b.1));
+    if 0.5 * diagonals.abs() < MIN_AREA {
+        return None;
+    }
+
+    let e = (b.0 - a.0, b.1 - a.1);
+    let f = (d.0 - a.0, d.1 - a.1);
+    let g = (a.0 - b.0 + c.0 - d.0, a.1 - b.1 + c.1 - d.1);
+    let h = (p.0 - a.0, p.1 - a.1);
+
+    let k2 = cross(g, f);
+    let k1 = cross(e, f) + cross(h, g);
+    let k0 = cross(h, e);
+
+    // Solve for u given v, using whichever axis is better conditioned.
+    let u_from_v = |v: f32| {
+        let den = (e.0 + g.0 * v, e.1 + g.1 * v);
+        if den.0.abs() > den.1.abs() {
+            (h.0 - f.0 * v) / den.0
+        } else {
+            (h.1 - f.1 * v) / den.1
+        }
+    };
+
+    let finite = |(u, v): (f32, f32)| (u.is_finite() && v.is_finite()).then_some((u, v));
+
+    // Parallel opposite edges; the equation is linear.
+    if k2.abs() < 1e-6 {
+        let v = -k0 / k1;
+        return finite((u_from_v(v), v));
+    }
+
+    let discriminant = (k1 * k1 - 4.0 * k0 * k2).max(0.0).sqrt();
+
+    let v = (-k1 - discriminant) / (2.0 * k2);
+    let u = u_from_v(v);
+    if (0.0..=1.0).contains(&u) && (0.0..=1.0).contains(&v) {
+        return Some((u, v));
+    }
+
+    let v = (-k1 + discriminant) / (2.0 * k2);
+    finite((u_from_v(v), v))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SKEWED: [(f32, f32); 4] = [(-0.8, -0.9), (0.9, -0.6), (0.7, 0.8), (-0.6, 0.9)];
+
+    fn bilinear(u: f32, v: f32, [a, b, c, d]: [(f32, f32); 4]) -> (f32, f32) {
+        let bottom = (lerp(u, a.0, b.0), lerp(u, a.1, b.1));
+        let top = (lerp(u, d.0, c.0), lerp(u, d.1, c.1));
+        (lerp(v, bottom.0, top.0), lerp(v, bottom.1, top.1))
+    }
+
+    fn assert_near((u, v): (f32, f32), expected: (f32, f32)) {
+        assert!(
+            (u - expected.0).abs() < 1e-4 && (v - expected.1).abs() < 1e-4,
+            "got {:?}, expected {expected:?}",
+            (u, v)
+        );
+    }
+
+    #[test]
+    fn corners_map_to_the_unit_square() {
+        let unit = [(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)];
+        for (corner, expected) in SKEWED.into_iter().zip(unit) {
+            assert_near(inv_bilinear(corner, SKEWED).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn centre_maps_to_the_middle() {
+        let centre = bilinear(0.5, 0.5, SKEWED);
+        assert_near(inv_bilinear(centre, SKEWED).unwrap(), (0.5, 0.5));
+
+        let default = Mapping::default().corners;
+        assert_near(inv_bilinear((0.0, 0.0), default).unwrap(), (0.5, 0.5));
+    }
+
+    #[test]
+    fn inside_points_round_trip() {
+        for (u, v) in [(0.25, 0.75), (0.9, 0.1), (0.6, 0.4)] {
+            let p = bilinear(u, v, SKEWED);
+            assert_near(inv_bilinear(p, SKEWED).unwrap(), (u, v));
+        }
+    }
+
+    #[test]
+    fn circle_clamp_keeps_diagonals_on_the_unit_circle() {
+        let circle = Mapping {
+            clamp_shape: ClampShape::Circle,
+            min_out_x: -2.0,
+            min_out_y: -2.0,
+            max_out_x: 2.0,
+            max_out_y: 2.0,
+            ..Default::default()
+        };
+
+        let (x, y) = circle.transform(1.0, 1.0, None);
+        assert!((x.hypot(y) - 1.0).abs() < 1e-6, "({x}, {y})");
+        assert!((x - y).abs() < 1e-6);
+
+        // Inside the circle, nothing is clamped.
+        assert_near(circle.transform(0.2, -0.1, None), (0.4, -0.2));
+
+        let square = Mapping {
+            clamp_shape: ClampShape::Square,
+            ..circle
+        };
+        assert_eq!(square.transform(1.0, 1.0, None), (1.0, 1.0));
+    }
+
+    #[test]
+    fn every_orientation_and_inversion_is_a_rotation_and_flip() {
+        const ORIENTATIONS: [MapOrientation; 4] = [
+            MapOrientation::None,
+            MapOrientation::A90,
+            MapOrientation::A180,
+            MapOrientation::A270,
+        ];
+
+        let (x, y) = (0.5, -0.25);
+        for orientation in ORIENTATIONS {
+            for invert_x in [false, true] {
+                for invert_y in [false, true] {
+                    for handedness in [Handedness::Right, Handedness::Left] {
+                        let mapping = Mapping {
+                            orientation,
+                            invert_x,
+                            invert_y,
+                            handedness,
+                            ..Default::default()
+                        };
+
+                        let flip_x = invert_x != (handedness == Handedness::Left);
+                        let flipped = (if flip_x { -x } else { x }, if invert_y { -y } else { y });
+                        let expected = match orientation {
+                            MapOrientation::None => flipped,
+                            MapOrientation::A90 => (-flipped.1, flipped.0),
+                            MapOrientation::A180 => (-flipped.0, -flipped.1),
+                            MapOrientation::A270 => (flipped.1, -flipped.0),
+                        };
+
+                        assert_near(mapping.transform(x, y, None), expected);
+                        assert_near(mapping.transform(0.0, 0.0, None), (0.0, 0.0));
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn debug_table_covers_the_grid() {
+        let table = Mapping::default().debug_table(3, None);
+        let lines: Vec<_> = table.lines().collect();
+
+        assert_eq!(lines.len(), 9);
+        assert_eq!(lines[0], "(-1.00, -1.00) -> (-1.000, -1.000)");
+        assert_eq!(lines[4], "(+0.00, +0.00) -> (+0.000, +0.000)");
+        assert_eq!(lines[8], "(+1.00, +1.00) -> (+1.000, +1.000)");
+    }
+
+    #[test]
+    fn pressure_at_or_below_the_minimum_is_no_contact() {
+        for pressure_invert in [false, true] {
+            let mapping = Mapping {
+                pressure_min: 100,
+                pressure_max: 1000,
+                pressure_invert,
+                ..Default::default()
+            };
+
+            assert_eq!(mapping.pressure(0), 0);
+            assert_eq!(mapping.pressure(100), 0);
+        }
+    }
+
+    #[test]
+    fn pressure_is_measured_from_the_end_of_the_range() {
+        let mapping = Mapping {
+            pressure_min: 100,
+            pressure_max: 1000,
+            ..Default::default()
+        };
+        assert_eq!(mapping.pressure(400), 300);
+        assert_eq!(mapping.pressure(5000), 900);
+
+        let inverted = Mapping {
+            pressure_invert: true,
+            ..mapping
+        };
+        assert_eq!(inverted.pressure(400), 600);
+        assert_eq!(inverted.pressure(1000), 0);
+    }
+
+    #[test]
+    fn degenerate_quad_falls_back_to_rect() {
+        let flat = [(-1.0, 0.0), (-0.5, 0.0), (0.5, 0.0), (1.0, 0.0)];
+        assert!(inv_bilinear((0.2, 0.0), flat).is_none());
+        assert!(inv_bilinear((0.2, 0.3), [(0.4, 0.4); 4]).is_none());
+
+        let quad = Mapping {
+            mode: MapMode::Quad,
+            corners: flat,
+            ..Default::default()
+        };
+        let (x, y) = quad.transform(0.2, 0.3, None);
+        assert!(x.is_finite() && y.is_finite());
+        assert_eq!((x, y), Mapping::default().transform(0.2, 0.3, None));
+    }
+}
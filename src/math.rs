@@ -37,6 +37,19 @@ pub fn angle_delta(a: f32, b: f32) -> f32 {
     delta
 }
 
+/// Nearest of `count` evenly spaced positions across `-half_range..=half_range`.
+/// A single position sits at the centre.
+pub fn nearest_detent(angle: f32, half_range: f32, count: u32) -> f32 {
+    if count <= 1 {
+        return 0.0;
+    }
+
+    let spacing = 2.0 * half_range / (count - 1) as f32;
+    let index = ((angle + half_range) / spacing).round();
+
+    -half_range + index * spacing
+}
+
 /// Adjust angle according to distance, up to a maximum.
 pub fn adjust_angle_delta(angle: f32, dist: f32, base: f32) -> f32 {
     let factor = dist.min(base) / base;
@@ -1,9 +1,20 @@
+/// Bit of `buttons` set while a second finger or contact touches a touch device.
+pub const SECOND_CONTACT_BIT: u8 = 7;
+
 #[derive(Debug, Default, Clone)]
 pub struct Pen {
     pub x: f32,
     pub y: f32,
     pub pressure: u32,
     pub buttons: u8,
+    /// Normalised hover distance, if the source reports it. 0.0 is closest.
+    pub distance: Option<f32>,
+    /// Normalised (X, Y) tilt, if the source reports it. (0.0, 0.0) is upright.
+    pub tilt: Option<(f32, f32)>,
+    /// Whether this is a repeat of an earlier sample because no new one arrived.
+    pub stale: bool,
+    /// Which end of the pen is in use.
+    pub tool: Tool,
 }
 
 #[derive(Debug, Default, Clone)]
@@ -12,4 +23,19 @@ pub struct RawPen {
     pub y: f32,
     pub pressure: u32,
     pub buttons: u8,
+    /// Normalised hover distance, if the source reports it. 0.0 is closest.
+    pub distance: Option<f32>,
+    /// Normalised (X, Y) tilt, if the source reports it. (0.0, 0.0) is upright.
+    pub tilt: Option<(f32, f32)>,
+    /// Whether this is a repeat of an earlier sample because no new one arrived.
+    pub stale: bool,
+    /// Which end of the pen is in use.
+    pub tool: Tool,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Tool {
+    #[default]
+    Pen,
+    Eraser,
 }
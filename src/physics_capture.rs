@@ -0,0 +1,90 @@
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use anyhow::{Context, Result};
+
+use crate::wheel::Wheel;
+
+/// How long a capture runs for.
+pub const CAPTURE_DURATION: Duration = Duration::from_secs(5);
+
+struct Sample {
+    time: f32,
+    angle: f32,
+    velocity: f32,
+    feedback_torque: f32,
+    honking: bool,
+    dragging: bool,
+}
+
+/// Wheel state recorded on every update for a short while, then written out as
+/// CSV to share how the wheel behaved.
+pub struct PhysicsCapture {
+    path: PathBuf,
+    start: Instant,
+    samples: Vec<Sample>,
+}
+
+impl PhysicsCapture {
+    pub fn new(path: PathBuf, update_frequency: u32) -> Self {
+        let capacity = (CAPTURE_DURATION.as_secs_f32() * update_frequency as f32) as usize;
+
+        Self {
+            path,
+            start: Instant::now(),
+            samples: Vec::with_capacity(capacity + 1),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Record the wheel's current state. Returns whether the capture is over.
+    pub fn record(&mut self, wheel: &Wheel) -> bool {
+        let elapsed = self.start.elapsed();
+        if elapsed >= CAPTURE_DURATION {
+            return true;
+        }
+
+        self.samples.push(Sample {
+            time: elapsed.as_secs_f32(),
+            angle: wheel.angle,
+            velocity: wheel.velocity,
+            feedback_torque: wheel.feedback_torque,
+            honking: wheel.honking,
+            dragging: wheel.dragging,
+        });
+
+        false
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let file = File::create(&self.path).context("Could not create physics capture file.")?;
+        let mut w = BufWriter::new(file);
+
+        writeln!(
+            &mut w,
+            "time_s,angle_rad,velocity_rad_s,feedback_torque_nm,honking,dragging"
+        )?;
+        for sample in &self.samples {
+            writeln!(
+                &mut w,
+                "{:.6},{},{},{},{},{}",
+                sample.time,
+                sample.angle,
+                sample.velocity,
+                sample.feedback_torque,
+                sample.honking,
+                sample.dragging
+            )?;
+        }
+
+        w.flush()?;
+        Ok(())
+    }
+}
@@ -1,4 +1,5 @@
 use std::{
+    ffi::OsString,
     fmt::Write as FmtWrite,
     fs::OpenOptions,
     io::{BufWriter, Read, Write},
@@ -6,11 +7,15 @@ use std::{
 };
 
 use anyhow::{Context, Result, bail};
-use log::error;
+use eframe::egui;
+use log::{error, info, warn};
 
 use crate::{
-    config::{Config, Device, Source},
-    mapping::MapOrientation,
+    config::{
+        AuxAxis, Config, Device, EraserAction, LockBehavior, MAX_SOURCE_POLL_RATE, MergePolicy,
+        Source, SteeringModel,
+    },
+    mapping::{ClampShape, Handedness, MapMode, MapOrientation, Mapping},
 };
 
 #[derive(Debug)]
@@ -34,65 +39,156 @@ pub fn save_file(config: &Config, path: &Path) -> Result<()> {
     writeln!(&mut w)?;
 
     writeln!(&mut w, "update_frequency = {}", config.update_frequency)?;
+    writeln!(&mut w, "fixed_timestep = {}", config.fixed_timestep)?;
+    writeln!(
+        &mut w,
+        "event_poll_frequency = {}",
+        config.event_poll_frequency
+    )?;
     writeln!(&mut w, "range = {}", config.range)?;
+    writeln!(&mut w, "lock_behavior = {:?}", config.lock_behavior)?;
+    writeln!(&mut w, "horn_enabled = {}", config.horn_enabled)?;
     writeln!(&mut w, "horn_radius = {}", config.horn_radius)?;
+    writeln!(&mut w, "horn_hysteresis = {}", config.horn_hysteresis)?;
+    writeln!(&mut w, "horn_cooldown = {}", config.horn_cooldown)?;
+    writeln!(&mut w, "horn_dwell_ms = {}", config.horn_dwell_ms)?;
     writeln!(&mut w, "pressure_threshold = {}", config.pressure_threshold)?;
+    writeln!(&mut w, "clutch_pressure = {}", config.clutch_pressure)?;
+    writeln!(
+        &mut w,
+        "recenter_button = {}",
+        config
+            .recenter_button
+            .map(|bit| bit.to_string())
+            .unwrap_or_default()
+    )?;
+    writeln!(
+        &mut w,
+        "engage_button = {}",
+        config
+            .engage_button
+            .map(|bit| bit.to_string())
+            .unwrap_or_default()
+    )?;
+    writeln!(&mut w, "eraser_action = {:?}", config.eraser_action)?;
+    writeln!(
+        &mut w,
+        "second_contact_horn = {}",
+        config.second_contact_horn
+    )?;
+    writeln!(&mut w, "steering_model = {:?}", config.steering_model)?;
+    writeln!(&mut w, "relative_engage = {}", config.relative_engage)?;
     writeln!(&mut w, "base_radius = {}", config.base_radius)?;
+    writeln!(&mut w, "sensitivity = {}", config.sensitivity)?;
     writeln!(&mut w)?;
 
     writeln!(&mut w, "inertia = {}", config.inertia)?;
     writeln!(&mut w, "friction = {}", config.friction)?;
+    writeln!(&mut w, "friction_quadratic = {}", config.friction_quadratic)?;
+    writeln!(&mut w, "release_damping = {}", config.release_damping)?;
     writeln!(&mut w, "spring = {}", config.spring)?;
+    writeln!(&mut w, "spring_exp = {}", config.spring_exp)?;
+    writeln!(&mut w, "max_spring_torque = {}", config.max_spring_torque)?;
+    writeln!(&mut w, "caster = {}", config.caster)?;
+    writeln!(&mut w, "detents = {}", config.detents)?;
+    writeln!(&mut w, "detent_strength = {}", config.detent_strength)?;
+    writeln!(&mut w, "center_assist_band = {}", config.center_assist_band)?;
+    writeln!(
+        &mut w,
+        "center_assist_strength = {}",
+        config.center_assist_strength
+    )?;
     writeln!(&mut w, "max_torque = {}", config.max_torque)?;
+    writeln!(&mut w, "feedback_slew = {}", config.feedback_slew)?;
+    writeln!(&mut w, "feedback_invert = {}", config.feedback_invert)?;
+    writeln!(
+        &mut w,
+        "feedback_rise_limit = {}",
+        config.feedback_rise_limit
+    )?;
+    writeln!(
+        &mut w,
+        "smoothness = {}",
+        config.smoothness.map(|s| s.to_string()).unwrap_or_default()
+    )?;
+    writeln!(&mut w, "slider_easing = {}", config.slider_easing)?;
+    writeln!(&mut w, "center_when_idle = {}", config.center_when_idle)?;
+    writeln!(&mut w)?;
+
+    write_mapping(&mut w, &config.mapping)?;
     writeln!(&mut w)?;
 
+    writeln!(&mut w, "net_sock_addr = {}", config.net_sock_addr)?;
+    writeln!(&mut w, "net_hold_last = {}", config.net_hold_last)?;
+    writeln!(&mut w, "net_lock_sender = {}", config.net_lock_sender)?;
+    writeln!(&mut w, "net_poll_rate = {}", config.net_poll_rate)?;
+    writeln!(&mut w, "net_max_drain = {}", config.net_max_drain)?;
+    writeln!(&mut w)?;
+
+    writeln!(&mut w, "panic_key = {}", config.panic_key)?;
     writeln!(
         &mut w,
-        "map_input_rect = {} {} {} {}",
-        config.mapping.min_in_x,
-        config.mapping.min_in_y,
-        config.mapping.max_in_x,
-        config.mapping.max_in_y
+        "pause_when_unfocused = {}",
+        config.pause_when_unfocused
     )?;
+    writeln!(&mut w, "output_ramp = {}", config.output_ramp)?;
+    writeln!(&mut w, "startup_delay_ms = {}", config.startup_delay_ms)?;
     writeln!(
         &mut w,
-        "map_output_rect = {} {} {} {}",
-        config.mapping.min_out_x,
-        config.mapping.min_out_y,
-        config.mapping.max_out_x,
-        config.mapping.max_out_y
+        "output_center_offset = {}",
+        config.output_center_offset
     )?;
+    writeln!(&mut w, "device_resolution = {}", config.device_resolution)?;
+    writeln!(&mut w, "device_smoothing = {}", config.device_smoothing)?;
+    writeln!(&mut w, "binding_horn = {}", config.bindings.horn)?;
+    for (i, name) in config.bindings.pen_buttons.iter().enumerate() {
+        writeln!(&mut w, "binding_pen_button{} = {name}", i + 1)?;
+    }
+    writeln!(&mut w, "binding_eraser = {}", config.bindings.eraser)?;
+    writeln!(&mut w, "binding_clutch = {}", config.bindings.clutch)?;
+    writeln!(&mut w, "lean_output = {}", config.lean_output)?;
+    writeln!(&mut w, "lean_axis = {:?}", config.lean_axis)?;
+    writeln!(&mut w, "horn_axis_output = {}", config.horn_axis_output)?;
+    writeln!(&mut w, "horn_axis = {:?}", config.horn_axis)?;
+    writeln!(&mut w, "device_name = {}", config.device_name)?;
     writeln!(
         &mut w,
-        "map_orientation = {}",
-        match config.mapping.orientation {
-            MapOrientation::None => "A0",
-            MapOrientation::A90 => "A90",
-            MapOrientation::A180 => "A180",
-            MapOrientation::A270 => "A270",
-        }
+        "device_id = {:04X} {:04X} {:04X}",
+        config.device_vendor, config.device_product, config.device_version
     )?;
+    writeln!(&mut w)?;
+
+    writeln!(&mut w, "vigem_scale = {}", config.vigem_scale)?;
+    writeln!(&mut w, "vigem_invert = {}", config.vigem_invert)?;
+    writeln!(&mut w, "vigem_max_rate = {}", config.vigem_max_rate)?;
+    writeln!(&mut w)?;
+
     writeln!(
         &mut w,
-        "map_invert = {}",
-        match (config.mapping.invert_x, config.mapping.invert_y) {
-            (false, false) => "",
-            (false, true) => "y",
-            (true, false) => "x",
-            (true, true) => "xy",
-        }
+        "keys_bindings = {} {}",
+        config.keys_left, config.keys_right
     )?;
+    writeln!(&mut w, "keys_deadzone = {}", config.keys_deadzone)?;
+    writeln!(&mut w, "keys_saturation = {}", config.keys_saturation)?;
     writeln!(&mut w)?;
 
-    writeln!(&mut w, "net_sock_addr = {}", config.net_sock_addr)?;
+    writeln!(
+        &mut w,
+        "mouse_pixels_per_degree = {}",
+        config.mouse_pixels_per_degree
+    )?;
+    writeln!(&mut w, "mouse_deadzone = {}", config.mouse_deadzone)?;
     writeln!(&mut w)?;
 
-    writeln!(&mut w, "device_resolution = {}", config.device_resolution)?;
-    writeln!(&mut w, "device_name = {}", config.device_name)?;
     writeln!(
         &mut w,
-        "device_id = {:04X} {:04X} {:04X}",
-        config.device_vendor, config.device_product, config.device_version
+        "wheel_base_image = {}",
+        config.wheel_base_image.as_deref().unwrap_or_default()
+    )?;
+    writeln!(
+        &mut w,
+        "wheel_inner_image = {}",
+        config.wheel_inner_image.as_deref().unwrap_or_default()
     )?;
     writeln!(&mut w)?;
 
@@ -101,15 +197,128 @@ pub fn save_file(config: &Config, path: &Path) -> Result<()> {
         "preferred_tablet = {}",
         config.preferred_tablet.as_deref().unwrap_or_default()
     )?;
+    writeln!(
+        &mut w,
+        "evdev_path = {}",
+        config
+            .evdev_path
+            .as_deref()
+            .map(|path| path.display().to_string())
+            .unwrap_or_default()
+    )?;
+    writeln!(&mut w, "evdev_poll_rate = {}", config.evdev_poll_rate)?;
+    writeln!(
+        &mut w,
+        "evdev_x_axis = {}",
+        config.evdev_x_axis.as_deref().unwrap_or_default()
+    )?;
+    writeln!(
+        &mut w,
+        "evdev_y_axis = {}",
+        config.evdev_y_axis.as_deref().unwrap_or_default()
+    )?;
     writeln!(&mut w)?;
 
     writeln!(&mut w, "source = {:?}", config.source)?;
+    writeln!(&mut w, "merge_source = {:?}", config.merge_source)?;
+    writeln!(&mut w, "merge_policy = {:?}", config.merge_policy)?;
     writeln!(&mut w, "device = {:?}", config.device)?;
     writeln!(&mut w)?;
 
     Ok(())
 }
 
+/// Save just the mapping, so it can be shared separately from the rest of the configuration.
+pub fn save_mapping_file(mapping: &Mapping, path: &Path) -> Result<()> {
+    let file = OpenOptions::new()
+        .write(true)
+        .truncate(true)
+        .create(true)
+        .open(path)
+        .context("Couldn't access file for writing.")?;
+    let mut w = BufWriter::new(file);
+
+    writeln!(&mut w, "; pen-steer mapping file")?;
+    writeln!(&mut w, "; this file is automatically generated")?;
+    writeln!(&mut w, ";")?;
+    writeln!(&mut w)?;
+
+    write_mapping(&mut w, mapping)?;
+
+    Ok(())
+}
+
+fn write_mapping(w: &mut impl Write, mapping: &Mapping) -> Result<()> {
+    writeln!(
+        w,
+        "map_mode = {}",
+        match mapping.mode {
+            MapMode::Rect => "rect",
+            MapMode::Quad => "quad",
+            MapMode::Physical => "physical",
+        }
+    )?;
+    writeln!(
+        w,
+        "map_input_rect = {} {} {} {}",
+        mapping.min_in_x, mapping.min_in_y, mapping.max_in_x, mapping.max_in_y
+    )?;
+    let [c0, c1, c2, c3] = mapping.corners;
+    writeln!(
+        w,
+        "map_input_quad = {} {} {} {} {} {} {} {}",
+        c0.0, c0.1, c1.0, c1.1, c2.0, c2.1, c3.0, c3.1
+    )?;
+    let (min_x, min_y, max_x, max_y) = mapping.area_mm;
+    writeln!(w, "map_input_mm = {min_x} {min_y} {max_x} {max_y}")?;
+    writeln!(
+        w,
+        "map_output_rect = {} {} {} {}",
+        mapping.min_out_x, mapping.min_out_y, mapping.max_out_x, mapping.max_out_y
+    )?;
+    writeln!(
+        w,
+        "map_orientation = {}",
+        match mapping.orientation {
+            MapOrientation::None => "A0",
+            MapOrientation::A90 => "A90",
+            MapOrientation::A180 => "A180",
+            MapOrientation::A270 => "A270",
+        }
+    )?;
+    writeln!(
+        w,
+        "map_invert = {}",
+        match (mapping.invert_x, mapping.invert_y) {
+            (false, false) => "",
+            (false, true) => "y",
+            (true, false) => "x",
+            (true, true) => "xy",
+        }
+    )?;
+    writeln!(
+        w,
+        "map_handedness = {}",
+        match mapping.handedness {
+            Handedness::Right => "right",
+            Handedness::Left => "left",
+        }
+    )?;
+    writeln!(
+        w,
+        "map_clamp_shape = {}",
+        match mapping.clamp_shape {
+            ClampShape::Square => "square",
+            ClampShape::Circle => "circle",
+        }
+    )?;
+    writeln!(w, "map_pressure_min = {}", mapping.pressure_min)?;
+    writeln!(w, "map_pressure_max = {}", mapping.pressure_max)?;
+    writeln!(w, "map_pressure_invert = {}", mapping.pressure_invert)?;
+
+    Ok(())
+}
+
 pub fn compile_parse_errors(errors: Vec<ParseError>) -> String {
     const MAX_ERRORS: usize = 30;
 
@@ -143,16 +352,88 @@ pub fn compile_parse_errors(errors: Vec<ParseError>) -> String {
 }
 
 pub fn load_file(config: &mut Config, path: &Path) -> Result<Vec<ParseError>> {
+    // Load into a copy, so that a file which can't be read leaves the source
+    // and device alone instead of switching them off.
+    let mut loaded = Config {
+        source: Source::None,
+        merge_source: Source::None,
+        device: Device::None,
+        ..config.clone()
+    };
+
+    let errors = load_lines(&mut loaded, path, false)?;
+    *config = loaded;
+
+    Ok(errors)
+}
+
+/// Load just the mapping, leaving the rest of the configuration untouched.
+pub fn load_mapping_file(mapping: &mut Mapping, path: &Path) -> Result<Vec<ParseError>> {
+    let mut config = Config {
+        mapping: mapping.clone(),
+        ..Default::default()
+    };
+
+    let errors = load_lines(&mut config, path, true)?;
+    *mapping = config.mapping;
+
+    Ok(errors)
+}
+
+/// Prefix of environment variables which override configuration items.
+const ENV_PREFIX: &str = "PENSTEER_";
+
+/// Shorter names accepted in place of some configuration items.
+const ENV_ALIASES: [(&str, &str); 1] = [("frequency", "update_frequency")];
+
+/// Apply `PENSTEER_<ITEM>` environment variables on top of the loaded
+/// configuration, where `<ITEM>` is any configuration item in upper case.
+/// Bad values are reported and otherwise ignored.
+pub fn load_env_overrides(config: &mut Config) {
+    apply_overrides(config, std::env::vars_os());
+}
+
+fn apply_overrides(config: &mut Config, vars: impl Iterator<Item = (OsString, OsString)>) {
+    // Other variables needn't be valid Unicode, so only ours are decoded.
+    for (name, value) in vars {
+        if !name.as_encoded_bytes().starts_with(ENV_PREFIX.as_bytes()) {
+            continue;
+        }
+
+        let (Some(name), Some(value)) = (name.to_str(), value.to_str()) else {
+            warn!("Ignoring {}: not valid UTF-8.", name.to_string_lossy());
+            continue;
+        };
+        let Some(item) = name.strip_prefix(ENV_PREFIX) else {
+            continue;
+        };
+
+        let mut key = item.to_lowercase();
+        if let Some((_, full)) = ENV_ALIASES.iter().find(|(alias, _)| *alias == key) {
+            key = full.to_string();
+        }
+
+        match load_from_line(config, &format!("{key} = {value}")) {
+            Ok(()) => info!("Using {key} = {value} from {name}."),
+            Err(err) => warn!("Ignoring {name}: {err}"),
+        }
+    }
+}
+
+fn load_lines(config: &mut Config, path: &Path, mapping_only: bool) -> Result<Vec<ParseError>> {
     let mut file = OpenOptions::new().read(true).open(path)?;
     let mut content = String::new();
     file.read_to_string(&mut content)?;
 
-    config.source = Source::None;
-    config.device = Device::None;
-
     let mut errors = vec![];
     for (line, text) in content.lines().enumerate() {
-        if let Err(err) = load_from_line(config, text.trim()) {
+        let result = if mapping_only {
+            load_mapping_from_line(config, text.trim())
+        } else {
+            load_from_line(config, text.trim())
+        };
+
+        if let Err(err) = result {
             let parse_err = ParseError {
                 line,
                 msg: err.to_string(),
@@ -173,6 +454,20 @@ pub fn load_file(config: &mut Config, path: &Path) -> Result<Vec<ParseError>> {
     Ok(errors)
 }
 
+fn load_mapping_from_line(config: &mut Config, text: &str) -> Result<()> {
+    // Ignore empty lines and comments.
+    if text.is_empty() || text.starts_with('#') || text.starts_with(';') {
+        return Ok(());
+    }
+
+    let (key, _) = tokenise_kv_line(text);
+    if !key.starts_with("map_") {
+        bail!("{key} is not a mapping item.");
+    }
+
+    load_from_line(config, text)
+}
+
 fn load_from_line(config: &mut Config, text: &str) -> Result<()> {
     // Ignore empty lines and comments.
     if text.is_empty() || text.starts_with('#') || text.starts_with(';') {
@@ -188,16 +483,67 @@ fn load_from_line(config: &mut Config, text: &str) -> Result<()> {
     const YES: f32 = 36000.0;
 
     match key {
-        "update_frequency" => config.update_frequency = parse_sane_u32(value, 5, 1000)?,
+        "update_frequency" => config.update_frequency = parse_sane_u32(value, 1, 2000)?,
+        "fixed_timestep" => config.fixed_timestep = parse_bool(value)?,
+        "event_poll_frequency" => config.event_poll_frequency = parse_sane_u32(value, 5, 1000)?,
         "range" => config.range = parse_sane_f32(value, 3.0, YES)?,
+        "lock_behavior" => config.lock_behavior = parse_lock_behavior(value)?,
+        "horn_enabled" => config.horn_enabled = parse_bool(value)?,
         "horn_radius" => config.horn_radius = parse_sane_f32(value, 0.0, YES)?,
+        "horn_hysteresis" => config.horn_hysteresis = parse_sane_f32(value, 0.0, YES)?,
+        "horn_cooldown" => config.horn_cooldown = parse_sane_f32(value, 0.0, 5.0)?,
+        "horn_dwell_ms" => config.horn_dwell_ms = parse_sane_u32(value, 0, 2000)?,
         "pressure_threshold" => config.pressure_threshold = parse_sane_u32(value, 0, u32::MAX)?,
-
+        "clutch_pressure" => config.clutch_pressure = parse_sane_u32(value, 0, u32::MAX)?,
+        "recenter_button" => {
+            config.recenter_button = if value.is_empty() {
+                None
+            } else {
+                Some(parse_sane_u32(value, 0, 7)? as u8)
+            }
+        }
+        "engage_button" => {
+            config.engage_button = if value.is_empty() {
+                None
+            } else {
+                Some(parse_sane_u32(value, 0, 7)? as u8)
+            }
+        }
+        "eraser_action" => config.eraser_action = parse_eraser_action(value)?,
+        "second_contact_horn" => config.second_contact_horn = parse_bool(value)?,
+        "steering_model" => config.steering_model = parse_steering_model(value)?,
+        "relative_engage" => config.relative_engage = parse_bool(value)?,
         "base_radius" => config.base_radius = parse_sane_f32(value, 0.0, YES)?,
+        "sensitivity" => config.sensitivity = parse_sane_f32(value, 0.01, 100.0)?,
         "inertia" => config.inertia = parse_sane_f32(value, 0.01, YES)?,
         "friction" => config.friction = parse_sane_f32(value, 0.0, YES)?,
+        "friction_quadratic" => config.friction_quadratic = parse_sane_f32(value, 0.0, YES)?,
+        "release_damping" => config.release_damping = parse_sane_f32(value, 0.0, YES)?,
         "spring" => config.spring = parse_sane_f32(value, -YES, YES)?,
+        "spring_exp" => config.spring_exp = parse_sane_f32(value, 0.5, 4.0)?,
+        "max_spring_torque" => config.max_spring_torque = parse_sane_f32(value, 0.0, YES)?,
+        "caster" => config.caster = parse_sane_f32(value, 0.0, YES)?,
+        "detents" => config.detents = parse_sane_u32(value, 0, 1000)?,
+        "detent_strength" => config.detent_strength = parse_sane_f32(value, 0.0, YES)?,
+        "center_assist_band" => config.center_assist_band = parse_sane_f32(value, 0.0, 180.0)?,
+        "center_assist_strength" => {
+            config.center_assist_strength = parse_sane_f32(value, 0.0, YES)?
+        }
         "max_torque" => config.max_torque = parse_sane_f32(value, -YES, YES)?,
+        "feedback_slew" => config.feedback_slew = parse_sane_f32(value, 0.0, 1000000.0)?,
+        "feedback_invert" => config.feedback_invert = parse_bool(value)?,
+        "feedback_rise_limit" => {
+            config.feedback_rise_limit = parse_sane_f32(value, 0.0, 1000000.0)?
+        }
+        "smoothness" => {
+            config.smoothness = if value.is_empty() {
+                None
+            } else {
+                Some(parse_sane_f32(value, 0.0, 1.0)?)
+            }
+        }
+        "slider_easing" => config.slider_easing = parse_sane_f32(value, 0.0, 5.0)?,
+        "center_when_idle" => config.center_when_idle = parse_bool(value)?,
 
         "map_input_rect" => {
             (
@@ -207,6 +553,9 @@ fn load_from_line(config: &mut Config, text: &str) -> Result<()> {
                 config.mapping.max_in_y,
             ) = parse_mapping_rect(value)?
         }
+        "map_mode" => config.mapping.mode = parse_mapping_mode(value)?,
+        "map_input_quad" => config.mapping.corners = parse_mapping_quad(value)?,
+        "map_input_mm" => config.mapping.area_mm = parse_mapping_rect(value)?,
         "map_output_rect" => {
             (
                 config.mapping.min_out_x,
@@ -220,9 +569,35 @@ fn load_from_line(config: &mut Config, text: &str) -> Result<()> {
             (config.mapping.invert_x, config.mapping.invert_y) = parse_mapping_invert(value)?
         }
 
-        "net_sock_addr" => config.net_sock_addr = value.to_owned(),
+        "map_handedness" => config.mapping.handedness = parse_handedness(value)?,
+        "map_clamp_shape" => config.mapping.clamp_shape = parse_clamp_shape(value)?,
+        "map_pressure_min" => config.mapping.pressure_min = parse_sane_u32(value, 0, u32::MAX)?,
+        "map_pressure_max" => config.mapping.pressure_max = parse_sane_u32(value, 1, u32::MAX)?,
+        "map_pressure_invert" => config.mapping.pressure_invert = parse_bool(value)?,
 
+        "net_sock_addr" => config.net_sock_addr = value.to_owned(),
+        "net_hold_last" => config.net_hold_last = parse_bool(value)?,
+        "net_lock_sender" => config.net_lock_sender = parse_bool(value)?,
+        "net_poll_rate" => config.net_poll_rate = parse_sane_u32(value, 0, MAX_SOURCE_POLL_RATE)?,
+        "net_max_drain" => config.net_max_drain = parse_sane_u32(value, 0, 100_000)?,
+
+        "panic_key" => config.panic_key = parse_panic_key(value)?,
+        "pause_when_unfocused" => config.pause_when_unfocused = parse_bool(value)?,
+        "output_ramp" => config.output_ramp = parse_sane_f32(value, 0.0, 10.0)?,
+        "startup_delay_ms" => config.startup_delay_ms = parse_sane_u32(value, 0, 60_000)?,
+        "output_center_offset" => config.output_center_offset = parse_sane_f32(value, -0.5, 0.5)?,
         "device_resolution" => config.device_resolution = parse_sane_u32(value, 2, 32768)?,
+        "device_smoothing" => config.device_smoothing = parse_bool(value)?,
+        "binding_horn" => config.bindings.horn = value.trim().to_owned(),
+        "binding_pen_button1" => config.bindings.pen_buttons[0] = value.trim().to_owned(),
+        "binding_pen_button2" => config.bindings.pen_buttons[1] = value.trim().to_owned(),
+        "binding_pen_button3" => config.bindings.pen_buttons[2] = value.trim().to_owned(),
+        "binding_eraser" => config.bindings.eraser = value.trim().to_owned(),
+        "binding_clutch" => config.bindings.clutch = value.trim().to_owned(),
+        "lean_output" => config.lean_output = parse_bool(value)?,
+        "lean_axis" => config.lean_axis = parse_aux_axis(value)?,
+        "horn_axis_output" => config.horn_axis_output = parse_bool(value)?,
+        "horn_axis" => config.horn_axis = parse_aux_axis(value)?,
         "device_name" => config.device_name = value.to_owned(),
         "device_id" => {
             (
@@ -232,11 +607,38 @@ fn load_from_line(config: &mut Config, text: &str) -> Result<()> {
             ) = parse_device_id(value)?
         }
 
+        "vigem_scale" => config.vigem_scale = parse_sane_f32(value, 0.01, 100.0)?,
+        "vigem_invert" => config.vigem_invert = parse_bool(value)?,
+        "vigem_max_rate" => config.vigem_max_rate = parse_sane_u32(value, 0, 10_000)?,
+
+        "keys_bindings" => (config.keys_left, config.keys_right) = parse_key_bindings(value)?,
+        "keys_deadzone" => config.keys_deadzone = parse_sane_f32(value, 0.0, 1.0)?,
+        "keys_saturation" => config.keys_saturation = parse_sane_f32(value, 0.0, 1.0)?,
+        "mouse_pixels_per_degree" => {
+            config.mouse_pixels_per_degree = parse_sane_f32(value, -1000.0, 1000.0)?
+        }
+        "mouse_deadzone" => config.mouse_deadzone = parse_sane_f32(value, 0.0, 90.0)?,
+
+        "wheel_base_image" => {
+            config.wheel_base_image = (!value.is_empty()).then(|| value.trim().to_owned())
+        }
+        "wheel_inner_image" => {
+            config.wheel_inner_image = (!value.is_empty()).then(|| value.trim().to_owned())
+        }
+
         "preferred_tablet" => {
             config.preferred_tablet = (!value.is_empty()).then(|| value.trim().to_owned())
         }
+        "evdev_path" => config.evdev_path = (!value.is_empty()).then(|| value.into()),
+        "evdev_poll_rate" => {
+            config.evdev_poll_rate = parse_sane_u32(value, 0, MAX_SOURCE_POLL_RATE)?
+        }
+        "evdev_x_axis" => config.evdev_x_axis = (!value.is_empty()).then(|| value.to_owned()),
+        "evdev_y_axis" => config.evdev_y_axis = (!value.is_empty()).then(|| value.to_owned()),
 
         "source" => config.source = parse_source(value)?,
+        "merge_source" => config.merge_source = parse_source(value)?,
+        "merge_policy" => config.merge_policy = parse_merge_policy(value)?,
         "device" => config.device = parse_device(value)?,
 
         _ => bail!("{key} is not a known configuration item."),
@@ -288,6 +690,14 @@ fn parse_sane_f32(text: &str, min: f32, max: f32) -> Result<f32> {
     Ok(x)
 }
 
+fn parse_bool(text: &str) -> Result<bool> {
+    Ok(match text.trim().to_lowercase().as_str() {
+        "true" | "yes" | "on" | "1" => true,
+        "false" | "no" | "off" | "0" => false,
+        _ => bail!("\"{text}\" is not a boolean."),
+    })
+}
+
 fn parse_device_id(text: &str) -> Result<(u16, u16, u16)> {
     let mut tokens = text.split_whitespace();
     let vendor = tokens.next().context("Missing vendor code.")?;
@@ -301,6 +711,14 @@ fn parse_device_id(text: &str) -> Result<(u16, u16, u16)> {
     ))
 }
 
+fn parse_key_bindings(text: &str) -> Result<(String, String)> {
+    let mut tokens = text.split_whitespace();
+    let left = tokens.next().context("Missing left key.")?;
+    let right = tokens.next().context("Missing right key.")?;
+
+    Ok((left.to_owned(), right.to_owned()))
+}
+
 fn parse_hex_u16(text: &str) -> Result<u16> {
     let clean = text.trim().trim_start_matches("0x");
     u16::from_str_radix(clean, 16).context("Invalid hexadecimal u16.")
@@ -321,6 +739,36 @@ fn parse_mapping_rect(text: &str) -> Result<(f32, f32, f32, f32)> {
     ))
 }
 
+fn parse_mapping_mode(text: &str) -> Result<MapMode> {
+    Ok(match text.trim().to_lowercase().as_str() {
+        "rect" => MapMode::Rect,
+        "quad" => MapMode::Quad,
+        "physical" => MapMode::Physical,
+        unknown => bail!("Unknown mapping mode \"{unknown}\""),
+    })
+}
+
+fn parse_mapping_quad(text: &str) -> Result<[(f32, f32); 4]> {
+    let mut tokens = text.split_whitespace();
+    let mut corners = [(0.0, 0.0); 4];
+
+    for (i, corner) in corners.iter_mut().enumerate() {
+        let x = tokens
+            .next()
+            .with_context(|| format!("Missing X of corner {i}."))?;
+        let y = tokens
+            .next()
+            .with_context(|| format!("Missing Y of corner {i}."))?;
+
+        *corner = (
+            parse_sane_f32(x, -1000000.0, 1000000.0)?,
+            parse_sane_f32(y, -1000000.0, 1000000.0)?,
+        );
+    }
+
+    Ok(corners)
+}
+
 fn parse_mapping_orientation(text: &str) -> Result<MapOrientation> {
     Ok(match text.trim() {
         "A0" => MapOrientation::None,
@@ -336,9 +784,9 @@ fn parse_mapping_invert(text: &str) -> Result<(bool, bool)> {
     let mut y = false;
 
     for c in text.chars() {
-        if c.to_ascii_lowercase() == 'x' {
+        if c.eq_ignore_ascii_case(&'x') {
             x = true;
-        } else if c.to_ascii_lowercase() == 'y' {
+        } else if c.eq_ignore_ascii_case(&'y') {
             y = true;
         } else if !c.is_whitespace() {
             bail!("Invalid axis character {c}!");
@@ -348,6 +796,64 @@ fn parse_mapping_invert(text: &str) -> Result<(bool, bool)> {
     Ok((x, y))
 }
 
+fn parse_panic_key(text: &str) -> Result<String> {
+    match egui::Key::from_name(text) {
+        Some(key) => Ok(key.name().to_owned()),
+        None => bail!("No such key \"{text}\"."),
+    }
+}
+
+fn parse_lock_behavior(text: &str) -> Result<LockBehavior> {
+    Ok(match text.to_lowercase().as_str() {
+        "hardstop" => LockBehavior::HardStop,
+        "bounce" => LockBehavior::Bounce,
+        _ => bail!("No such \"{text}\" lock behaviour."),
+    })
+}
+
+fn parse_aux_axis(text: &str) -> Result<AuxAxis> {
+    Ok(match text.to_lowercase().as_str() {
+        "y" => AuxAxis::Y,
+        "z" => AuxAxis::Z,
+        "rx" => AuxAxis::Rx,
+        "ry" => AuxAxis::Ry,
+        "rz" => AuxAxis::Rz,
+        _ => bail!("No such \"{text}\" axis."),
+    })
+}
+
+fn parse_steering_model(text: &str) -> Result<SteeringModel> {
+    Ok(match text.to_lowercase().as_str() {
+        "radial" => SteeringModel::Radial,
+        "planar" => SteeringModel::Planar,
+        _ => bail!("No such \"{text}\" steering model."),
+    })
+}
+
+fn parse_eraser_action(text: &str) -> Result<EraserAction> {
+    Ok(match text.to_lowercase().as_str() {
+        "steer" => EraserAction::Steer,
+        "horn" => EraserAction::Horn,
+        _ => bail!("No such \"{text}\" eraser action."),
+    })
+}
+
+fn parse_handedness(text: &str) -> Result<Handedness> {
+    Ok(match text.trim().to_lowercase().as_str() {
+        "right" => Handedness::Right,
+        "left" => Handedness::Left,
+        unknown => bail!("Unknown handedness \"{unknown}\""),
+    })
+}
+
+fn parse_clamp_shape(text: &str) -> Result<ClampShape> {
+    Ok(match text.trim().to_lowercase().as_str() {
+        "square" => ClampShape::Square,
+        "circle" => ClampShape::Circle,
+        unknown => bail!("Unknown clamp shape \"{unknown}\""),
+    })
+}
+
 fn parse_source(text: &str) -> Result<Source> {
     Ok(match text.to_lowercase().as_str() {
         "" | "none" => Source::None,
@@ -360,13 +866,86 @@ fn parse_source(text: &str) -> Result<Source> {
     })
 }
 
+fn parse_merge_policy(text: &str) -> Result<MergePolicy> {
+    Ok(match text.trim().to_lowercase().as_str() {
+        "buttons" => MergePolicy::Buttons,
+        "pressureandbuttons" => MergePolicy::PressureAndButtons,
+        unknown => bail!("Unknown merge policy \"{unknown}\""),
+    })
+}
+
 fn parse_device(text: &str) -> Result<Device> {
     Ok(match text.to_lowercase().as_str() {
         "" | "none" => Device::None,
         #[cfg(target_os = "linux")]
         "uinput" => Device::UInput,
+        #[cfg(target_os = "linux")]
+        "keys" => Device::Keys,
+        #[cfg(target_os = "linux")]
+        "mouse" => Device::Mouse,
         #[cfg(target_os = "windows")]
         "vigembus" => Device::VigemBus,
         _ => bail!("No such \"{text}\" device."),
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+
+    fn configured() -> Config {
+        Config {
+            source: Source::Net,
+            merge_source: Source::Net,
+            range: 540.0,
+            ..Default::default()
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn overrides_skip_other_and_undecodable_variables() {
+        use std::os::unix::ffi::OsStringExt;
+
+        let undecodable = || OsString::from_vec(vec![0xff, 0xfe]);
+        let vars = [
+            (OsString::from("UNRELATED"), undecodable()),
+            (OsString::from("PENSTEER_RANGE"), OsString::from("720")),
+            (OsString::from("PENSTEER_SPRING"), undecodable()),
+        ];
+
+        let mut config = Config::default();
+        apply_overrides(&mut config, vars.into_iter());
+
+        assert_eq!(config.range, 720.0);
+        assert_eq!(config.spring, Config::default().spring);
+    }
+
+    #[test]
+    fn unreadable_file_changes_nothing() {
+        let mut config = configured();
+        let path = std::env::temp_dir().join("pen-steer-test-missing.cfg");
+
+        assert!(load_file(&mut config, &path).is_err());
+        assert_eq!(config.source, Source::Net);
+        assert_eq!(config.merge_source, Source::Net);
+        assert_eq!(config.range, 540.0);
+    }
+
+    #[test]
+    fn loaded_file_replaces_source_and_device() {
+        let mut config = configured();
+        let path = std::env::temp_dir().join(format!("pen-steer-test-{}.cfg", std::process::id()));
+        fs::write(&path, "range = 720\n").unwrap();
+
+        let result = load_file(&mut config, &path);
+        fs::remove_file(&path).unwrap();
+
+        assert!(result.unwrap().is_empty());
+        assert_eq!(config.range, 720.0);
+        assert_eq!(config.source, Source::None);
+        assert_eq!(config.merge_source, Source::None);
+    }
+}
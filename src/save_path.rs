@@ -1,8 +1,19 @@
-use std::{path::PathBuf, str::FromStr};
+use std::{path::PathBuf, str::FromStr, sync::OnceLock};
 
 use directories::ProjectDirs;
 
+static SAVE_PATH_OVERRIDE: OnceLock<PathBuf> = OnceLock::new();
+
+/// Use `path` as the configuration file instead of the default one.
+pub fn override_save_path(path: PathBuf) {
+    let _ = SAVE_PATH_OVERRIDE.set(path);
+}
+
 pub fn save_path() -> PathBuf {
+    if let Some(path) = SAVE_PATH_OVERRIDE.get() {
+        return path.clone();
+    }
+
     save_dir().join("pen-steer.conf")
 }
 
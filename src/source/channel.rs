@@ -0,0 +1,37 @@
+use std::sync::{
+    Mutex,
+    mpsc::{self, Receiver, Sender},
+};
+
+use crate::{pen::RawPen, source::Source};
+
+/// Source fed by code rather than a device, e.g. for benchmarks or embedding.
+///
+/// It has no `config::Source` entry; put it in `State::source` directly and
+/// leave `reset_source` unset, or it will be replaced by the configured source.
+#[derive(Debug)]
+pub struct ChannelSource {
+    // `Receiver` is not `Sync`, but it is only ever used through `&mut self`.
+    receiver: Mutex<Receiver<RawPen>>,
+}
+
+impl ChannelSource {
+    /// Create the source along with the handle used to push samples into it.
+    pub fn new() -> (Self, Sender<RawPen>) {
+        let (sender, receiver) = mpsc::channel();
+
+        (
+            Self {
+                receiver: Mutex::new(receiver),
+            },
+            sender,
+        )
+    }
+}
+
+impl Source for ChannelSource {
+    fn get(&mut self) -> Option<RawPen> {
+        // Only the latest sample matters, like with the other sources.
+        self.receiver.get_mut().unwrap().try_iter().last()
+    }
+}
@@ -2,50 +2,102 @@ use std::{
     fmt::Debug,
     fs::{self, DirEntry, File, OpenOptions},
     os::unix::fs::OpenOptionsExt,
+    path::Path,
 };
 
 use anyhow::{Context, Result, bail};
-use input_linux::{AbsoluteAxis, EvdevHandle, EventKind, EventRef};
+use input_linux::{AbsoluteAxis, EvdevHandle, EventKind, EventRef, Key, sys::BUS_BLUETOOTH};
 use log::{debug, info, trace};
 use nix::libc::O_NONBLOCK;
 
-use crate::{pen::RawPen, source::Source};
+use crate::{
+    config::Config,
+    pen::{RawPen, SECOND_CONTACT_BIT, Tool},
+    source::Source,
+};
 
 pub struct EvdevSource {
     handle: EvdevHandle<File>,
+    device_name: String,
+    /// Axes read as the pen's X and Y.
+    axes: (AbsoluteAxis, AbsoluteAxis),
     x_min: i32,
     x_max: i32,
     y_min: i32,
     y_max: i32,
+    distance_range: Option<(i32, i32)>,
+    /// Tilt ranges, in the order: X min, X max, Y min, Y max.
+    tilt_range: Option<(i32, i32, i32, i32)>,
+    /// Multitouch position ranges, used when plain X/Y are not updating.
+    mt_range: Option<(i32, i32, i32, i32)>,
+    contacts: Contacts,
+    /// Whether there is a plain pressure axis. Without one, pressure comes from
+    /// the followed multitouch contact, or from whether anything is touching.
+    has_pressure: bool,
+    has_mt_pressure: bool,
+    has_mt_tracking: bool,
+    /// Pressure reported for a touch, on devices which can't tell how hard it is.
+    touch_pressure: u32,
     aspect_ratio: f32,
+    /// Width and height in millimetres, if the axes report their resolution.
+    physical_size: Option<(f32, f32)>,
     current: RawPen,
+    description: String,
 }
 
 impl EvdevSource {
-    pub fn new(preferred_device_name: Option<&str>) -> Result<Self> {
-        let device_name;
-
-        if let Some(dev) = preferred_device_name {
-            device_name = dev.trim().to_string();
-        } else {
-            debug!("No source device preference.");
-            let devices = enumerate_available_devices()?;
-            if let Some(first) = devices.first() {
-                device_name = first.clone();
-            } else {
-                bail!("No valid input devices available! (evdev)");
+    pub fn new(config: &Config) -> Result<Self> {
+        let EvdevDeviceHandle {
+            handle,
+            name: device_name,
+            bus,
+        } = match &config.evdev_path {
+            Some(path) => {
+                debug!("Using source device at: {}", path.display());
+                open_evdev_tablet_path(path)
+                    .with_context(|| format!("Failed to open {}.", path.display()))?
             }
-        }
-
-        debug!("Using source device: {device_name}");
-
-        let Some(handle) =
-            open_device_with_name(&device_name).context("Failed to open evdev device.")?
-        else {
-            bail!("No such device found.");
+            None => open_preferred_device(config.preferred_tablet.as_deref())?,
         };
 
-        let (x_min, x_max, y_min, y_max) = get_dimensions(&handle)?;
+        info!("Opened source device: {device_name}");
+
+        let axis_x = config
+            .evdev_x_axis
+            .as_deref()
+            .map(parse_axis_name)
+            .transpose()?;
+        let axis_y = config
+            .evdev_y_axis
+            .as_deref()
+            .map(parse_axis_name)
+            .transpose()?;
+        let axes = (
+            axis_x.unwrap_or(AbsoluteAxis::X),
+            axis_y.unwrap_or(AbsoluteAxis::Y),
+        );
+
+        let (x_min, x_max, y_min, y_max, physical_size, mt_range);
+        if axes == (AbsoluteAxis::X, AbsoluteAxis::Y) {
+            (x_min, x_max, y_min, y_max) = get_dimensions(&handle)?;
+            physical_size = get_physical_size(&handle);
+            mt_range = get_multitouch_dimensions(&handle);
+        } else {
+            info!("Steering with the {:?} and {:?} axes.", axes.0, axes.1);
+            (x_min, x_max) = get_axis_range(&handle, axes.0)?;
+            (y_min, y_max) = get_axis_range(&handle, axes.1)?;
+            // Other axes have no meaningful size, and multitouch would fight them.
+            physical_size = None;
+            mt_range = None;
+        }
+        let distance_range = get_distance_range(&handle);
+        let tilt_range = get_tilt_range(&handle);
+        let has_pressure = has_axis(&handle, AbsoluteAxis::Pressure);
+        let has_mt_pressure = has_axis(&handle, AbsoluteAxis::MultitouchPressure);
+        let has_mt_tracking = has_axis(&handle, AbsoluteAxis::MultitouchTrackingId);
+        if !has_pressure && !has_mt_pressure {
+            info!("No pressure axis; touches will read as full pressure.");
+        }
         let width = x_max - x_min;
         let height = y_max - y_min;
         let aspect_ratio = width as f32 / height as f32;
@@ -56,61 +108,270 @@ impl EvdevSource {
 
         info!("Initialised!");
 
+        let description = format!(
+            "evdev tablet\n\
+            \tName: {device_name}\n\
+            \tBus: {bus:#x}\n\
+            \tX ({:?}): {x_min} .. {x_max}, Y ({:?}): {y_min} .. {y_max}\n\
+            \tSize (mm): {physical_size:?}\n\
+            \tDistance: {distance_range:?}\n\
+            \tTilt: {tilt_range:?}\n\
+            \tMultitouch: {mt_range:?}\n\
+            \tAxes: {:?}\n\
+            \tKeys: {:?}",
+            axes.0,
+            axes.1,
+            handle
+                .absolute_bits()
+                .map(|bits| bits.iter().collect::<Vec<_>>())
+                .unwrap_or_default(),
+            handle
+                .key_bits()
+                .map(|bits| bits.iter().collect::<Vec<_>>())
+                .unwrap_or_default(),
+        );
+
         Ok(Self {
             handle,
+            device_name,
+            axes,
             x_min,
             x_max,
             y_min,
             y_max,
+            distance_range,
+            tilt_range,
+            mt_range,
+            contacts: Contacts::default(),
+            has_pressure,
+            has_mt_pressure,
+            has_mt_tracking,
+            touch_pressure: config.mapping.pressure_max,
             aspect_ratio,
+            physical_size,
             current: RawPen::default(),
+            description,
         })
     }
 }
 
-impl Source for EvdevSource {
-    fn get(&mut self) -> Option<RawPen> {
-        fn norm(t: i32, a1: i32, a2: i32) -> f32 {
-            ((-1.0) + (t as f64 - a1 as f64) * (1.0 - (-1.0)) / (a2 as f64 - a1 as f64)) as f32
+impl EvdevSource {
+    fn set_x(&mut self, value: i32, min: i32, max: i32) {
+        self.current.x = norm(value, min, max);
+        if self.aspect_ratio > 1.0 {
+            self.current.x = (self.current.x * self.aspect_ratio).clamp(-1.0, 1.0);
         }
+    }
+
+    fn set_y(&mut self, value: i32, min: i32, max: i32) {
+        self.current.y = norm(value, min, max);
+        if self.aspect_ratio < 1.0 {
+            self.current.y = (self.current.y * (1.0 / self.aspect_ratio)).clamp(-1.0, 1.0);
+        }
+    }
 
+    /// Stand in for pressure when the device has no plain pressure axis.
+    fn set_touching(&mut self, touching: bool) {
+        if !touching {
+            self.current.pressure = 0;
+        } else if !self.has_mt_pressure {
+            self.current.pressure = self.touch_pressure;
+        }
+    }
+
+    fn set_button(&mut self, bit: u8, pressed: bool) {
+        if pressed {
+            self.current.buttons |= 1 << bit;
+        } else {
+            self.current.buttons &= !(1 << bit);
+        }
+    }
+}
+
+impl Source for EvdevSource {
+    fn get(&mut self) -> Option<RawPen> {
         let mut changed = false;
+        let mut plain_position_changed = false;
+        let mut mt_x = None;
+        let mut mt_y = None;
 
         while let Ok(event) = self.handle.read_input_event() {
             let Ok(event) = EventRef::new(&event) else {
                 continue;
             };
 
-            let EventRef::Absolute(abs) = event else {
-                continue;
+            let abs = match event {
+                EventRef::Absolute(abs) => abs,
+                EventRef::Key(key) => {
+                    let pressed = key.value.is_pressed();
+                    match key.key {
+                        // Only present on devices which tell the tip and eraser apart.
+                        Key::ButtonToolPen if pressed => self.current.tool = Tool::Pen,
+                        Key::ButtonToolRubber if pressed => self.current.tool = Tool::Eraser,
+                        Key::ButtonStylus => self.set_button(0, pressed),
+                        Key::ButtonStylus2 => self.set_button(1, pressed),
+                        Key::ButtonStylus3 => self.set_button(2, pressed),
+                        // Multitouch tracking tells the contacts apart, when there is any.
+                        Key::ButtonTouch if !self.has_pressure && !self.has_mt_tracking => {
+                            self.set_touching(pressed)
+                        }
+                        _ => continue,
+                    }
+                    changed = true;
+                    continue;
+                }
+                _ => continue,
             };
 
             match abs.axis {
-                AbsoluteAxis::X => {
-                    self.current.x = norm(abs.value, self.x_min, self.x_max);
-                    if self.aspect_ratio > 1.0 {
-                        self.current.x = (self.current.x * self.aspect_ratio).clamp(-1.0, 1.0);
-                    }
+                axis if axis == self.axes.0 => {
+                    self.set_x(abs.value, self.x_min, self.x_max);
+                    plain_position_changed = true;
                     changed = true;
                 }
-                AbsoluteAxis::Y => {
-                    self.current.y = norm(abs.value, self.y_min, self.y_max);
-                    if self.aspect_ratio < 1.0 {
-                        self.current.y =
-                            (self.current.y * (1.0 / self.aspect_ratio)).clamp(-1.0, 1.0);
+                axis if axis == self.axes.1 => {
+                    self.set_y(abs.value, self.y_min, self.y_max);
+                    plain_position_changed = true;
+                    changed = true;
+                }
+                AbsoluteAxis::MultitouchSlot => self.contacts.slot = abs.value,
+                AbsoluteAxis::MultitouchTrackingId => {
+                    self.contacts.set_tracking_id(abs.value);
+                    self.set_button(SECOND_CONTACT_BIT, self.contacts.second_down());
+                    if !self.has_pressure {
+                        self.set_touching(self.contacts.tracked_down());
                     }
                     changed = true;
                 }
+                AbsoluteAxis::MultitouchPositionX if self.contacts.in_tracked_slot() => {
+                    mt_x = Some(abs.value);
+                }
+                AbsoluteAxis::MultitouchPositionY if self.contacts.in_tracked_slot() => {
+                    mt_y = Some(abs.value);
+                }
                 AbsoluteAxis::Pressure => {
                     self.current.pressure = abs.value.max(0) as u32;
                     changed = true;
                 }
+                AbsoluteAxis::MultitouchPressure
+                    if !self.has_pressure && self.contacts.in_tracked_slot() =>
+                {
+                    self.current.pressure = abs.value.max(0) as u32;
+                    changed = true;
+                }
+                AbsoluteAxis::Distance => {
+                    if let Some((min, max)) = self.distance_range {
+                        let distance = 0.5 * (norm(abs.value, min, max) + 1.0);
+                        self.current.distance = Some(distance.clamp(0.0, 1.0));
+                        changed = true;
+                    }
+                }
+                AbsoluteAxis::TiltX => {
+                    if let Some((min, max, _, _)) = self.tilt_range {
+                        let tilt = self.current.tilt.get_or_insert_default();
+                        tilt.0 = norm(abs.value, min, max).clamp(-1.0, 1.0);
+                        changed = true;
+                    }
+                }
+                AbsoluteAxis::TiltY => {
+                    if let Some((_, _, min, max)) = self.tilt_range {
+                        let tilt = self.current.tilt.get_or_insert_default();
+                        tilt.1 = norm(abs.value, min, max).clamp(-1.0, 1.0);
+                        changed = true;
+                    }
+                }
                 _ => {}
             }
         }
 
+        // Hybrid touch/pen tablets may only report position on multitouch axes.
+        if !plain_position_changed && let Some((x_min, x_max, y_min, y_max)) = self.mt_range {
+            if let Some(x) = mt_x {
+                self.set_x(x, x_min, x_max);
+                changed = true;
+            }
+
+            if let Some(y) = mt_y {
+                self.set_y(y, y_min, y_max);
+                changed = true;
+            }
+        }
+
         changed.then_some(self.current.clone())
     }
+
+    fn device_name(&self) -> Option<&str> {
+        Some(&self.device_name)
+    }
+
+    fn physical_size(&self) -> Option<(f32, f32)> {
+        self.physical_size
+    }
+
+    fn describe(&self) -> String {
+        self.description.clone()
+    }
+}
+
+/// Which multitouch slots have a contact, and which one is being followed.
+#[derive(Debug, Default)]
+struct Contacts {
+    /// Slot the following multitouch events are for.
+    slot: i32,
+    /// Slot of the contact being steered with, until it lifts.
+    tracked: Option<i32>,
+    /// Bitmask of the slots which currently have a contact.
+    down: u32,
+}
+
+impl Contacts {
+    /// Whether multitouch events currently belong to the contact being followed.
+    fn in_tracked_slot(&self) -> bool {
+        self.tracked.is_none_or(|slot| slot == self.slot)
+    }
+
+    /// Handle a tracking ID for the current slot, which is negative when its
+    /// contact lifts. The first contact is followed until it lifts.
+    fn set_tracking_id(&mut self, id: i32) {
+        if id < 0 {
+            if self.tracked == Some(self.slot) {
+                self.tracked = None;
+            }
+        } else if self.tracked.is_none() {
+            self.tracked = Some(self.slot);
+        }
+
+        let Some(bit) = slot_bit(self.slot) else {
+            return;
+        };
+
+        if id < 0 {
+            self.down &= !bit;
+        } else {
+            self.down |= bit;
+        }
+    }
+
+    /// Whether the followed contact is down.
+    fn tracked_down(&self) -> bool {
+        self.tracked.is_some()
+    }
+
+    /// Whether any contact besides the followed one is down.
+    fn second_down(&self) -> bool {
+        let tracked_bit = self.tracked.and_then(slot_bit).unwrap_or(0);
+        self.down & !tracked_bit != 0
+    }
+}
+
+fn slot_bit(slot: i32) -> Option<u32> {
+    u32::try_from(slot)
+        .ok()
+        .and_then(|slot| 1u32.checked_shl(slot))
+}
+
+fn norm(t: i32, a1: i32, a2: i32) -> f32 {
+    ((-1.0) + (t as f64 - a1 as f64) * (1.0 - (-1.0)) / (a2 as f64 - a1 as f64)) as f32
 }
 
 impl Debug for EvdevSource {
@@ -119,7 +380,28 @@ impl Debug for EvdevSource {
     }
 }
 
-pub fn enumerate_available_devices() -> Result<Vec<String>> {
+/// A tablet found while enumerating evdev devices.
+#[derive(Debug, Clone)]
+pub struct EvdevDeviceInfo {
+    pub name: String,
+    /// Bus type, e.g. USB or Bluetooth.
+    pub bus: u16,
+    /// Names of the absolute axes it reports.
+    pub axes: Vec<String>,
+}
+
+impl EvdevDeviceInfo {
+    /// Name for display, noting the connection if it is unusual.
+    pub fn label(&self) -> String {
+        if self.bus == BUS_BLUETOOTH {
+            format!("{} (Bluetooth)", self.name)
+        } else {
+            self.name.clone()
+        }
+    }
+}
+
+pub fn enumerate_available_devices() -> Result<Vec<EvdevDeviceInfo>> {
     let mut valid_devices = vec![];
 
     for entry in fs::read_dir("/dev/input/")? {
@@ -135,15 +417,52 @@ pub fn enumerate_available_devices() -> Result<Vec<String>> {
                 continue;
             }
         };
-        
-        trace!("Found valid input: {}", handle.name);
-        valid_devices.push(handle.name);
+
+        trace!("Found valid input: {} (bus {:#x})", handle.name, handle.bus);
+        let axes = handle
+            .handle
+            .absolute_bits()
+            .map(|bits| bits.iter().map(|axis| format!("{axis:?}")).collect())
+            .unwrap_or_default();
+        valid_devices.push(EvdevDeviceInfo {
+            name: handle.name,
+            bus: handle.bus,
+            axes,
+        });
     }
 
     Ok(valid_devices)
 }
 
-fn open_device_with_name(target_name: &str) -> Result<Option<EvdevHandle<File>>> {
+/// Open the tablet with the preferred name, or the first one found if there is
+/// no preference.
+fn open_preferred_device(preferred_device_name: Option<&str>) -> Result<EvdevDeviceHandle> {
+    let device_name;
+
+    if let Some(dev) = preferred_device_name {
+        device_name = dev.trim().to_string();
+    } else {
+        debug!("No source device preference.");
+        let devices = enumerate_available_devices()?;
+        if let Some(first) = devices.first() {
+            device_name = first.name.clone();
+        } else {
+            bail!("No valid input devices available! (evdev)");
+        }
+    }
+
+    debug!("Using source device: {device_name}");
+
+    let Some(handle) =
+        open_device_with_name(&device_name).context("Failed to open evdev device.")?
+    else {
+        bail!("No such device found.");
+    };
+
+    Ok(handle)
+}
+
+fn open_device_with_name(target_name: &str) -> Result<Option<EvdevDeviceHandle>> {
     for entry in fs::read_dir("/dev/input/")? {
         let Ok(entry) = entry else {
             continue;
@@ -159,7 +478,7 @@ fn open_device_with_name(target_name: &str) -> Result<Option<EvdevHandle<File>>>
         };
 
         if handle.name.contains(target_name) {
-            return Ok(Some(handle.handle));
+            return Ok(Some(handle));
         }
     }
 
@@ -169,6 +488,7 @@ fn open_device_with_name(target_name: &str) -> Result<Option<EvdevHandle<File>>>
 struct EvdevDeviceHandle {
     handle: EvdevHandle<File>,
     name: String,
+    bus: u16,
 }
 
 fn open_evdev_tablet_device(entry: DirEntry) -> Result<EvdevDeviceHandle> {
@@ -186,10 +506,16 @@ fn open_evdev_tablet_device(entry: DirEntry) -> Result<EvdevDeviceHandle> {
         bail!("Not a device file.");
     }
 
+    open_evdev_tablet_path(&entry.path())
+}
+
+/// Open the device node at `path`, which may be a link such as those in
+/// `/dev/input/by-id`, checking that it looks like a tablet.
+fn open_evdev_tablet_path(path: &Path) -> Result<EvdevDeviceHandle> {
     let file = OpenOptions::new()
         .read(true)
         .custom_flags(O_NONBLOCK)
-        .open(entry.path())?;
+        .open(path)?;
 
     let handle = input_linux::EvdevHandle::new(file);
 
@@ -204,33 +530,55 @@ fn open_evdev_tablet_device(entry: DirEntry) -> Result<EvdevDeviceHandle> {
     let mut has_x = false;
     let mut has_y = false;
     let mut has_pressure = false;
+    let mut has_touch = false;
 
     for abs in &abs {
         match abs {
-            AbsoluteAxis::X => has_x = true,
-            AbsoluteAxis::Y => has_y = true,
-            AbsoluteAxis::Pressure => has_pressure = true,
+            AbsoluteAxis::X | AbsoluteAxis::MultitouchPositionX => has_x = true,
+            AbsoluteAxis::Y | AbsoluteAxis::MultitouchPositionY => has_y = true,
+            AbsoluteAxis::Pressure | AbsoluteAxis::MultitouchPressure => has_pressure = true,
+            // Touch devices may only say whether something is touching.
+            AbsoluteAxis::MultitouchTrackingId => has_touch = true,
             _ => (),
         }
     }
 
-    if !has_x || !has_y || !has_pressure {
-        bail!("Input device must have X, Y, and pressure axes.");
+    let keys = handle.key_bits();
+    has_touch |= keys.as_ref().is_ok_and(|keys| keys.get(Key::ButtonTouch));
+
+    // Some (often Bluetooth) tablets briefly report an axis as missing, but
+    // still advertise a pen tool, which is a good enough sign.
+    let has_pen_tool = keys.is_ok_and(|keys| keys.get(Key::ButtonToolPen));
+
+    if !has_pen_tool && (!has_x || !has_y || !(has_pressure || has_touch)) {
+        bail!("Input device must have X and Y axes with pressure or touch, or a pen tool.");
     }
 
     let mut dev_name = handle.device_name()?;
-    
+
     // Remove nul terminator.
-    if dev_name.len() > 0 && dev_name[dev_name.len() - 1] == b'\0' {
+    if !dev_name.is_empty() && dev_name[dev_name.len() - 1] == b'\0' {
         dev_name.pop();
     }
 
     let name = String::from_utf8_lossy(&dev_name).into_owned();
+    let bus = handle.device_id().map(|id| id.bustype).unwrap_or_default();
+
+    if !has_x || !has_y || !(has_pressure || has_touch) {
+        debug!("Accepting {name} for its pen tool despite missing axes.");
+    }
 
-    Ok(EvdevDeviceHandle { handle, name })
+    Ok(EvdevDeviceHandle { handle, name, bus })
 }
 
 fn get_dimensions(handle: &EvdevHandle<File>) -> Result<(i32, i32, i32, i32)> {
+    if !has_axis(handle, AbsoluteAxis::X)
+        && let Some(dimensions) = get_multitouch_dimensions(handle)
+    {
+        debug!("No plain X/Y axes; using multitouch dimensions.");
+        return Ok(dimensions);
+    }
+
     let info_x = handle
         .absolute_info(AbsoluteAxis::X)
         .context("Could not get X axis info.")?;
@@ -245,3 +593,152 @@ fn get_dimensions(handle: &EvdevHandle<File>) -> Result<(i32, i32, i32, i32)> {
         info_y.maximum,
     ))
 }
+
+/// Size of the area covered by the X/Y axes (or multitouch axes, if there are
+/// no plain ones), using the resolution they report in units per millimetre.
+fn get_physical_size(handle: &EvdevHandle<File>) -> Option<(f32, f32)> {
+    let (axis_x, axis_y) = if has_axis(handle, AbsoluteAxis::X) {
+        (AbsoluteAxis::X, AbsoluteAxis::Y)
+    } else {
+        (
+            AbsoluteAxis::MultitouchPositionX,
+            AbsoluteAxis::MultitouchPositionY,
+        )
+    };
+
+    let info_x = handle.absolute_info(axis_x).ok()?;
+    let info_y = handle.absolute_info(axis_y).ok()?;
+    if info_x.resolution <= 0 || info_y.resolution <= 0 {
+        debug!("Axes do not report a resolution; physical size is unknown.");
+        return None;
+    }
+
+    Some((
+        (info_x.maximum - info_x.minimum) as f32 / info_x.resolution as f32,
+        (info_y.maximum - info_y.minimum) as f32 / info_y.resolution as f32,
+    ))
+}
+
+/// Range of a single axis, which the device must report.
+fn get_axis_range(handle: &EvdevHandle<File>, axis: AbsoluteAxis) -> Result<(i32, i32)> {
+    if !has_axis(handle, axis) {
+        bail!("Device has no {axis:?} axis.");
+    }
+
+    let info = handle.absolute_info(axis)?;
+    Ok((info.minimum, info.maximum))
+}
+
+/// Look up an absolute axis by its name, e.g. `X` or `TiltX`.
+pub fn parse_axis_name(name: &str) -> Result<AbsoluteAxis> {
+    let name = name.trim();
+
+    AbsoluteAxis::iter()
+        .find(|axis| format!("{axis:?}").eq_ignore_ascii_case(name))
+        .with_context(|| format!("No such axis \"{name}\"."))
+}
+
+fn has_axis(handle: &EvdevHandle<File>, axis: AbsoluteAxis) -> bool {
+    handle
+        .absolute_bits()
+        .is_ok_and(|bits| bits.iter().any(|abs| abs == axis))
+}
+
+fn get_multitouch_dimensions(handle: &EvdevHandle<File>) -> Option<(i32, i32, i32, i32)> {
+    if !has_axis(handle, AbsoluteAxis::MultitouchPositionX)
+        || !has_axis(handle, AbsoluteAxis::MultitouchPositionY)
+    {
+        return None;
+    }
+
+    let info_x = handle
+        .absolute_info(AbsoluteAxis::MultitouchPositionX)
+        .ok()?;
+    let info_y = handle
+        .absolute_info(AbsoluteAxis::MultitouchPositionY)
+        .ok()?;
+
+    Some((
+        info_x.minimum,
+        info_x.maximum,
+        info_y.minimum,
+        info_y.maximum,
+    ))
+}
+
+fn get_distance_range(handle: &EvdevHandle<File>) -> Option<(i32, i32)> {
+    if !has_axis(handle, AbsoluteAxis::Distance) {
+        debug!("No distance axis; hover distance will not be reported.");
+        return None;
+    }
+
+    let info = handle.absolute_info(AbsoluteAxis::Distance).ok()?;
+    (info.maximum > info.minimum).then_some((info.minimum, info.maximum))
+}
+
+fn get_tilt_range(handle: &EvdevHandle<File>) -> Option<(i32, i32, i32, i32)> {
+    if !has_axis(handle, AbsoluteAxis::TiltX) || !has_axis(handle, AbsoluteAxis::TiltY) {
+        debug!("No tilt axes; tilt will not be reported.");
+        return None;
+    }
+
+    let info_x = handle.absolute_info(AbsoluteAxis::TiltX).ok()?;
+    let info_y = handle.absolute_info(AbsoluteAxis::TiltY).ok()?;
+    (info_x.maximum > info_x.minimum && info_y.maximum > info_y.minimum).then_some((
+        info_x.minimum,
+        info_x.maximum,
+        info_y.minimum,
+        info_y.maximum,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn touch(contacts: &mut Contacts, slot: i32, id: i32) {
+        contacts.slot = slot;
+        contacts.set_tracking_id(id);
+    }
+
+    #[test]
+    fn first_contact_is_followed() {
+        let mut contacts = Contacts::default();
+        assert!(!contacts.tracked_down());
+
+        touch(&mut contacts, 0, 10);
+        assert!(contacts.tracked_down());
+        assert!(!contacts.second_down());
+
+        touch(&mut contacts, 0, -1);
+        assert!(!contacts.tracked_down());
+    }
+
+    #[test]
+    fn second_contact_is_reported_while_the_first_steers() {
+        let mut contacts = Contacts::default();
+        touch(&mut contacts, 0, 10);
+        touch(&mut contacts, 1, 11);
+        assert!(contacts.second_down());
+        assert!(!contacts.in_tracked_slot());
+
+        contacts.slot = 0;
+        assert!(contacts.in_tracked_slot());
+
+        touch(&mut contacts, 1, -1);
+        assert!(contacts.tracked_down());
+        assert!(!contacts.second_down());
+    }
+
+    #[test]
+    fn lifting_the_first_contact_leaves_the_second() {
+        let mut contacts = Contacts::default();
+        touch(&mut contacts, 0, 10);
+        touch(&mut contacts, 1, 11);
+        touch(&mut contacts, 0, -1);
+
+        // The remaining finger is not steering, so it still counts as a second contact.
+        assert!(!contacts.tracked_down());
+        assert!(contacts.second_down());
+    }
+}
@@ -0,0 +1,149 @@
+use std::net::SocketAddr;
+
+use crate::{config::MergePolicy, pen::RawPen, source::Source};
+
+/// Two sources read together, e.g. a tablet for steering and a phone for buttons.
+///
+/// The primary source decides where the pen is. The latest sample from each is
+/// kept, so that a new sample from either one is merged with the other's last.
+pub struct MergedSource {
+    primary: Box<dyn Source>,
+    secondary: Box<dyn Source>,
+    policy: MergePolicy,
+    last_primary: Option<RawPen>,
+    last_secondary: Option<RawPen>,
+}
+
+impl MergedSource {
+    pub fn new(primary: Box<dyn Source>, secondary: Box<dyn Source>, policy: MergePolicy) -> Self {
+        Self {
+            primary,
+            secondary,
+            policy,
+            last_primary: None,
+            last_secondary: None,
+        }
+    }
+}
+
+impl Source for MergedSource {
+    fn get(&mut self) -> Option<RawPen> {
+        let primary = self.primary.get();
+        let secondary = self.secondary.get();
+        if primary.is_none() && secondary.is_none() {
+            return None;
+        }
+
+        if primary.is_some() {
+            self.last_primary = primary;
+        }
+        if secondary.is_some() {
+            self.last_secondary = secondary;
+        }
+
+        // Nothing can be reported until the primary has said where the pen is.
+        let mut pen = self.last_primary.clone()?;
+        if let Some(secondary) = &self.last_secondary {
+            pen.buttons |= secondary.buttons;
+            if self.policy == MergePolicy::PressureAndButtons {
+                pen.pressure = secondary.pressure;
+            }
+        }
+
+        Some(pen)
+    }
+
+    fn device_name(&self) -> Option<&str> {
+        self.primary.device_name()
+    }
+
+    fn peer(&self) -> Option<SocketAddr> {
+        self.primary.peer().or_else(|| self.secondary.peer())
+    }
+
+    fn physical_size(&self) -> Option<(f32, f32)> {
+        self.primary.physical_size()
+    }
+
+    fn describe(&self) -> String {
+        format!(
+            "{}\n\
+            Merged with ({}):\n{}",
+            self.primary.describe(),
+            self.policy,
+            self.secondary.describe()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::mpsc::Sender;
+
+    use super::*;
+    use crate::source::channel::ChannelSource;
+
+    fn merged(policy: MergePolicy) -> (MergedSource, Sender<RawPen>, Sender<RawPen>) {
+        let (primary, primary_sender) = ChannelSource::new();
+        let (secondary, secondary_sender) = ChannelSource::new();
+        let source = MergedSource::new(Box::new(primary), Box::new(secondary), policy);
+
+        (source, primary_sender, secondary_sender)
+    }
+
+    fn pen(x: f32, pressure: u32, buttons: u8) -> RawPen {
+        RawPen {
+            x,
+            pressure,
+            buttons,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn nothing_without_new_samples() {
+        let (mut source, primary, _secondary) = merged(MergePolicy::Buttons);
+        assert!(source.get().is_none());
+
+        primary.send(pen(0.5, 100, 0)).unwrap();
+        assert!(source.get().is_some());
+        assert!(source.get().is_none());
+    }
+
+    #[test]
+    fn secondary_only_update_is_merged() {
+        let (mut source, primary, secondary) = merged(MergePolicy::PressureAndButtons);
+
+        // No position yet, so there is nothing to report.
+        secondary.send(pen(0.0, 300, 0b10)).unwrap();
+        assert!(source.get().is_none());
+
+        primary.send(pen(0.5, 100, 0b01)).unwrap();
+        let merged = source.get().unwrap();
+        assert_eq!(
+            (merged.x, merged.pressure, merged.buttons),
+            (0.5, 300, 0b11)
+        );
+
+        // Only the secondary changes; the primary's last position is kept.
+        secondary.send(pen(0.0, 700, 0)).unwrap();
+        let merged = source.get().unwrap();
+        assert_eq!(
+            (merged.x, merged.pressure, merged.buttons),
+            (0.5, 700, 0b01)
+        );
+    }
+
+    #[test]
+    fn buttons_policy_keeps_primary_pressure() {
+        let (mut source, primary, secondary) = merged(MergePolicy::Buttons);
+
+        primary.send(pen(-0.25, 100, 0)).unwrap();
+        secondary.send(pen(0.0, 300, 0b100)).unwrap();
+        let merged = source.get().unwrap();
+        assert_eq!(
+            (merged.x, merged.pressure, merged.buttons),
+            (-0.25, 100, 0b100)
+        );
+    }
+}
@@ -1,16 +1,44 @@
+pub mod channel;
 #[cfg(target_os = "linux")]
 pub mod evdev;
+pub mod merge;
 pub mod net;
 
-use crate::{config, pen::RawPen, source::net::NetSource};
+use crate::{
+    config,
+    pen::RawPen,
+    source::{merge::MergedSource, net::NetSource},
+};
 
 #[cfg(target_os = "linux")]
 use crate::source::evdev::EvdevSource;
 
-use anyhow::Result;
+use std::net::SocketAddr;
+
+use anyhow::{Result, bail};
 
 pub trait Source: Send + Sync {
     fn get(&mut self) -> Option<RawPen>;
+
+    /// Name of the underlying input device, if there is one.
+    fn device_name(&self) -> Option<&str> {
+        None
+    }
+
+    /// Address of the remote sender currently being listened to, if any.
+    fn peer(&self) -> Option<SocketAddr> {
+        None
+    }
+
+    /// Width and height of the tablet's active area in millimetres, if known.
+    fn physical_size(&self) -> Option<(f32, f32)> {
+        None
+    }
+
+    /// Human-readable summary of the source's capabilities, for bug reports.
+    fn describe(&self) -> String {
+        "No details available.".into()
+    }
 }
 
 pub struct DummySource;
@@ -19,15 +47,38 @@ impl Source for DummySource {
     fn get(&mut self) -> Option<RawPen> {
         None
     }
+
+    fn describe(&self) -> String {
+        "Dummy source; no input.".into()
+    }
 }
 
 pub fn create_source(config: &config::Config) -> Result<Box<dyn Source>> {
-    Ok(match config.source {
+    // Both would fight over the same device or port.
+    if config.merge_source != config::Source::None && config.merge_source == config.source {
+        bail!("A source can't be merged with another of the same kind.");
+    }
+
+    let source = create_single_source(config.source, config)?;
+    if config.merge_source == config::Source::None {
+        return Ok(source);
+    }
+
+    let secondary = create_single_source(config.merge_source, config)?;
+    Ok(Box::new(MergedSource::new(
+        source,
+        secondary,
+        config.merge_policy,
+    )))
+}
+
+fn create_single_source(kind: config::Source, config: &config::Config) -> Result<Box<dyn Source>> {
+    Ok(match kind {
         config::Source::None => Box::new(DummySource),
-        config::Source::Net => Box::new(NetSource::new(&config.net_sock_addr)?),
+        config::Source::Net => Box::new(NetSource::new(config)?),
         #[cfg(target_os = "windows")]
         config::Source::Wintab => Box::new(DummySource),
         #[cfg(target_os = "linux")]
-        config::Source::Evdev => Box::new(EvdevSource::new(config.preferred_tablet.as_deref())?),
+        config::Source::Evdev => Box::new(EvdevSource::new(config)?),
     })
 }
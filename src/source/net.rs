@@ -1,16 +1,29 @@
 use anyhow::Result;
 use log::info;
-use std::net::UdpSocket;
+use std::{
+    net::{SocketAddr, UdpSocket},
+    time::{Duration, Instant},
+};
 
-use crate::{pen::RawPen, source::Source};
+use crate::{config::Config, pen::RawPen, source::Source};
+
+/// How long a locked sender may stay silent before another can take over.
+const SENDER_LOCK_TIMEOUT: Duration = Duration::from_secs(2);
 
 #[derive(Debug)]
 pub struct NetSource {
     socket: UdpSocket,
+    hold_last: bool,
+    last: Option<RawPen>,
+    lock_sender: bool,
+    locked_sender: Option<(SocketAddr, Instant)>,
+    /// Most packets read in one call to `get`. 0 is unlimited.
+    max_drain: u32,
 }
 
 impl NetSource {
-    pub fn new(addr: &str) -> Result<Self> {
+    pub fn new(config: &Config) -> Result<Self> {
+        let addr = &config.net_sock_addr;
         let socket = UdpSocket::bind(addr)?;
         socket.set_nonblocking(true)?;
 
@@ -18,18 +31,70 @@ impl NetSource {
 
         Ok(Self {
             socket,
+            hold_last: config.net_hold_last,
+            last: None,
+            lock_sender: config.net_lock_sender,
+            locked_sender: None,
+            max_drain: config.net_max_drain,
         })
     }
 }
 
 impl Source for NetSource {
     fn get(&mut self) -> Option<RawPen> {
+        let pen = self.receive();
+
+        if !self.hold_last {
+            return pen;
+        }
+
+        match pen {
+            Some(pen) => {
+                self.last = Some(pen.clone());
+                Some(pen)
+            }
+            None => self.last.clone().map(|last| RawPen {
+                stale: true,
+                ..last
+            }),
+        }
+    }
+
+    fn peer(&self) -> Option<SocketAddr> {
+        self.locked_sender.map(|(addr, _)| addr)
+    }
+
+    fn describe(&self) -> String {
+        let addr = self
+            .socket
+            .local_addr()
+            .map_or_else(|err| err.to_string(), |addr| addr.to_string());
+
+        format!(
+            "UDP net source\n\
+            \tBound to: {addr}\n\
+            \tHold last: {}, lock sender: {}, max drain: {}",
+            self.hold_last, self.lock_sender, self.max_drain
+        )
+    }
+}
+
+impl NetSource {
+    /// Drain the socket, up to the packet limit, returning the latest packet if
+    /// any arrived. Packets past the limit are left for the next call.
+    fn receive(&mut self) -> Option<RawPen> {
         let mut pen = RawPen::default();
         let mut buf = [0u8; 13];
         let mut filled = false;
+        let mut drained = 0;
 
         loop {
-            let Some((len, _)) = self.socket.recv_from(&mut buf).ok() else {
+            if self.max_drain != 0 && drained >= self.max_drain {
+                return filled.then_some(pen);
+            }
+            drained += 1;
+
+            let Some((len, sender)) = self.socket.recv_from(&mut buf).ok() else {
                 return filled.then_some(pen);
             };
 
@@ -37,6 +102,10 @@ impl Source for NetSource {
                 return filled.then_some(pen);
             }
 
+            if !self.accept_sender(sender) {
+                continue;
+            }
+
             filled = true;
             pen.x = f32::from_le_bytes(buf[0..4].try_into().unwrap());
             pen.y = f32::from_le_bytes(buf[4..8].try_into().unwrap());
@@ -44,4 +113,64 @@ impl Source for NetSource {
             pen.buttons = buf[12];
         }
     }
+
+    /// Whether to use a packet from `sender`, locking onto it if nobody else is.
+    fn accept_sender(&mut self, sender: SocketAddr) -> bool {
+        if !self.lock_sender {
+            return true;
+        }
+
+        let now = Instant::now();
+
+        if let Some((locked, last_seen)) = self.locked_sender
+            && locked != sender
+            && now - last_seen < SENDER_LOCK_TIMEOUT
+        {
+            return false;
+        }
+
+        if self
+            .locked_sender
+            .is_none_or(|(locked, _)| locked != sender)
+        {
+            info!("Locked onto sender {sender}");
+        }
+
+        self.locked_sender = Some((sender, now));
+
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn packet(x: f32) -> [u8; 13] {
+        let mut packet = [0u8; 13];
+        packet[0..4].copy_from_slice(&x.to_le_bytes());
+        packet
+    }
+
+    #[test]
+    fn draining_stops_at_the_cap() {
+        let mut source = NetSource::new(&Config {
+            net_sock_addr: "127.0.0.1:0".into(),
+            net_max_drain: 2,
+            ..Default::default()
+        })
+        .unwrap();
+        let addr = source.socket.local_addr().unwrap();
+
+        let sender = UdpSocket::bind("127.0.0.1:0").unwrap();
+        for x in 1..=5 {
+            sender.send_to(&packet(x as f32), addr).unwrap();
+        }
+
+        // The latest of each batch, with the rest left for the next call.
+        assert_eq!(source.get().map(|pen| pen.x), Some(2.0));
+        assert_eq!(source.get().map(|pen| pen.x), Some(4.0));
+        assert_eq!(source.get().map(|pen| pen.x), Some(5.0));
+        assert!(source.get().is_none());
+    }
 }
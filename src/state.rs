@@ -1,19 +1,30 @@
 use anyhow::anyhow;
 use log::{debug, warn};
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
 
 use crate::{
-    config::Config,
+    config::{self, Config},
     device::Device,
-    pen::Pen,
-    save::{compile_parse_errors, load_file},
+    input_dump::InputDump,
+    pen::{Pen, RawPen},
+    physics_capture::PhysicsCapture,
+    save::{compile_parse_errors, load_env_overrides, load_file},
     save_path::save_path,
     source::Source,
     wheel::Wheel,
 };
 
+/// How many controller loop intervals are kept for the tick timing view.
+pub const TICK_HISTORY: usize = 2000;
+
 pub struct State {
     pub wheel: Wheel,
     pub pen: Option<Pen>,
+    /// Last pen sample as the source gave it, before mapping. Only for display.
+    pub raw_pen: Option<RawPen>,
     pub pen_override: Option<Pen>,
     pub source: Option<Box<dyn Source>>,
     pub device: Option<Box<dyn Device>>,
@@ -21,9 +32,66 @@ pub struct State {
     pub last_error: Option<anyhow::Error>,
     pub reset_source: bool,
     pub reset_device: bool,
+    /// Kind of source currently open, or `None` if there is none.
+    pub active_source: config::Source,
+    /// Kind of device currently created, or `None` if there is none or it fell back.
+    pub active_device: config::Device,
+    /// Set when the configured device could not be created and a dummy is used instead.
+    pub device_fallback: bool,
+    /// When the output started easing in after the device was created.
+    pub output_ramp_start: Option<Instant>,
+    /// When the source is next due to be polled.
+    pub next_source_poll: Option<Instant>,
+    /// When the physics last ran, and the real time since not yet simulated,
+    /// when using a fixed timestep.
+    pub last_physics_update: Option<Instant>,
+    pub physics_time_debt: Duration,
+    /// When the source last produced a fresh sample.
+    pub last_source_sample: Option<Instant>,
+    /// When the device last applied its state successfully.
+    pub last_device_apply: Option<Instant>,
+    /// Whether the recenter button was down on the previous update.
+    pub recenter_held: bool,
+    /// Output is held neutral and the simulation is stopped until resumed.
+    pub paused: bool,
+    /// Whether the GUI window is open but not focused.
+    pub gui_unfocused: bool,
+    /// Output is held neutral because the GUI window lost focus.
+    pub focus_paused: bool,
+    /// Hold the wheel still instead of simulating it while it is not being dragged.
+    pub freeze_physics: bool,
+    /// Measured time between recent controller timer wakeups, oldest first.
+    pub tick_intervals: VecDeque<Duration>,
+    /// Where fresh source samples are logged as CSV, if anywhere.
+    pub input_dump: Option<InputDump>,
+    /// Wheel state being recorded on every update, if a capture is running.
+    pub physics_capture: Option<PhysicsCapture>,
+    /// Set to ask the GUI to hide its window, leaving the controller running.
+    pub hide_gui: bool,
 }
 
 impl State {
+    pub fn record_tick_interval(&mut self, interval: Duration) {
+        if self.tick_intervals.len() >= TICK_HISTORY {
+            self.tick_intervals.pop_front();
+        }
+        self.tick_intervals.push_back(interval);
+    }
+
+    /// Physics updates per second, measured over the most recent wakeups.
+    pub fn measured_update_rate(&self) -> Option<f32> {
+        const WINDOW: usize = 100;
+
+        let recent = self.tick_intervals.iter().rev().take(WINDOW);
+        let count = recent.len();
+        if count == 0 {
+            return None;
+        }
+
+        let mean = recent.sum::<Duration>().as_secs_f32() / count as f32;
+        (mean > 0.0).then(|| 1.0 / (mean * self.config.polls_per_update() as f32))
+    }
+
     pub fn create() -> Self {
         let mut state = Self::default();
 
@@ -38,10 +106,10 @@ impl State {
             Err(load_err) => {
                 // Do not show error if it just does not exist.
                 let mut escalate_error = true;
-                if let Some(err) = load_err.downcast_ref::<std::io::Error>() {
-                    if let std::io::ErrorKind::NotFound = err.kind() {
-                        escalate_error = false;
-                    }
+                if let Some(err) = load_err.downcast_ref::<std::io::Error>()
+                    && let std::io::ErrorKind::NotFound = err.kind()
+                {
+                    escalate_error = false;
                 }
                 
                 if escalate_error {
@@ -52,15 +120,36 @@ impl State {
             }
         }
 
+        load_env_overrides(&mut state.config);
+
         state
     }
 }
 
+impl State {
+    /// Immediately neutralise the output and stop simulating the wheel.
+    pub fn pause(&mut self) {
+        if !self.paused {
+            warn!("Paused; output neutralised.");
+        }
+
+        self.paused = true;
+        self.wheel.honking = false;
+        self.wheel.dragging = false;
+    }
+
+    pub fn resume(&mut self) {
+        self.paused = false;
+        self.output_ramp_start = Some(Instant::now());
+    }
+}
+
 impl Default for State {
     fn default() -> Self {
         Self {
             wheel: Wheel::default(),
             pen: None,
+            raw_pen: None,
             pen_override: None,
             source: None,
             device: None,
@@ -68,6 +157,24 @@ impl Default for State {
             last_error: None,
             reset_source: true,
             reset_device: true,
+            active_source: config::Source::None,
+            active_device: config::Device::None,
+            device_fallback: false,
+            output_ramp_start: None,
+            next_source_poll: None,
+            last_physics_update: None,
+            physics_time_debt: Duration::ZERO,
+            last_source_sample: None,
+            last_device_apply: None,
+            recenter_held: false,
+            paused: false,
+            gui_unfocused: false,
+            focus_paused: false,
+            freeze_physics: false,
+            tick_intervals: VecDeque::with_capacity(TICK_HISTORY),
+            input_dump: None,
+            physics_capture: None,
+            hide_gui: false,
         }
     }
 }
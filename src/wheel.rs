@@ -1,6 +1,15 @@
 use eframe::egui::Pos2;
+use log::{info, warn};
 
-use crate::{config::Config, device::Device, math, pen::Pen};
+use crate::{
+    config::{Config, EraserAction, LockBehavior, SteeringModel},
+    device::Device,
+    math,
+    pen::{Pen, Tool},
+};
+
+/// Time (in seconds) over which the extra damping after letting go fades out.
+const RELEASE_DAMPING_TIME: f32 = 0.5;
 
 #[derive(Debug, Default, Clone)]
 pub struct Wheel {
@@ -11,39 +20,248 @@ pub struct Wheel {
     pub dragging: bool,
     pub prev_pos: Pos2,
     pub prev_angle: f32,
+    /// Angle between the wheel and the pen when it landed, kept for relative engage.
+    pub engage_offset: f32,
+    /// Seconds left until pressing the centre sounds the horn again.
+    pub horn_cooldown: f32,
+    /// Seconds the pen has rested in the centre without sounding the horn yet.
+    pub horn_dwell: f32,
+    /// Angle held by the GUI slider while it is being dragged.
+    pub manual_target: Option<f32>,
+    /// Whether the feedback rise limiter held the torque back on the last update.
+    pub rise_limited: bool,
+    /// Seconds left of the extra damping applied after letting go of the wheel.
+    pub release_damping_left: f32,
 }
 
 impl Wheel {
-    pub fn update(
+    /// Ease the wheel towards the angle given by the pen's horizontal position.
+    fn steer_planar(
         &mut self,
-        mut device: Option<&mut Box<dyn Device>>,
+        device: Option<&mut Box<dyn Device>>,
         config: &Config,
-        pen: Option<Pen>,
+        pen: &Pen,
+        half_range: f32,
         dt: f32,
     ) {
-        let half_range = config.half_range_rad();
+        const SMOOTHING: f32 = 0.05;
 
-        let pen = pen.unwrap_or_default();
+        // Pen X is positive towards the left of the wheel.
+        let pen_angle = -pen.x.clamp(-1.0, 1.0) * half_range;
 
-        if self.velocity.is_nan() || self.velocity.is_infinite() {
-            self.velocity = 0.0;
+        // On first contact, remember how far off the pen is so the wheel doesn't jump.
+        if !self.dragging {
+            self.engage_offset = if config.relative_engage {
+                self.angle - pen_angle
+            } else {
+                0.0
+            };
         }
 
-        if self.angle.is_nan() || self.angle.is_infinite() {
-            self.angle = 0.0;
+        let target = pen_angle + self.engage_offset;
+        let blend = 1.0 - (-dt / SMOOTHING).exp();
+
+        self.prev_angle = self.angle;
+        self.angle = math::clamp_symmetric(half_range, self.angle + (target - self.angle) * blend);
+        self.velocity = (self.angle - self.prev_angle) / dt;
+        self.dragging = true;
+
+        if let Some(dev) = device {
+            dev.set_wheel(self.angle / half_range);
         }
+    }
 
-        if !self.dragging {
-            let feedback_normalised = device
+    /// Ease the wheel towards the angle held by the GUI slider instead of simulating it.
+    fn follow_manual_target(
+        &mut self,
+        device: Option<&mut Box<dyn Device>>,
+        config: &Config,
+        target: f32,
+        half_range: f32,
+        dt: f32,
+    ) {
+        let blend = if config.slider_easing > 0.0 {
+            1.0 - (-dt / config.slider_easing).exp()
+        } else {
+            1.0
+        };
+
+        self.prev_angle = self.angle;
+        self.angle = math::clamp_symmetric(half_range, self.angle + (target - self.angle) * blend);
+        self.velocity = (self.angle - self.prev_angle) / dt;
+
+        if let Some(dev) = device {
+            dev.set_wheel(self.angle / half_range);
+        }
+    }
+
+    /// Keep the wheel within its range, stopping or bouncing it off the lock.
+    fn stop_at_lock(&mut self, config: &Config, half_range: f32) {
+        /// Fraction of the velocity kept when bouncing off the lock.
+        const RESTITUTION: f32 = 0.3;
+
+        if self.angle.abs() <= half_range {
+            return;
+        }
+
+        self.angle = math::clamp_symmetric(half_range, self.angle);
+
+        // only if still heading into the lock
+        if self.velocity * self.angle > 0.0 {
+            self.velocity = match config.lock_behavior {
+                LockBehavior::HardStop => 0.0,
+                LockBehavior::Bounce => -self.velocity * RESTITUTION,
+            };
+        }
+    }
+
+    /// Torque pulling a slow, free wheel back into the nearest detent.
+    fn detent_torque(&self, config: &Config, half_range: f32) -> f32 {
+        const MAX_VELOCITY: f32 = 2.0;
+
+        if config.detents == 0 || self.velocity.abs() > MAX_VELOCITY {
+            return 0.0;
+        }
+
+        let detent = math::nearest_detent(self.angle, half_range, config.detents);
+        config.detent_strength * (self.angle - detent)
+    }
+
+    /// Centring torque, following a curve towards lock if `spring_exp` isn't 1.
+    fn spring_torque(&self, config: &Config, half_range: f32) -> f32 {
+        let torque = if config.spring_exp == 1.0 || half_range <= 0.0 {
+            config.spring * self.angle
+        } else {
+            let progress = (self.angle / half_range).abs().powf(config.spring_exp);
+            config.spring * self.angle.signum() * progress * half_range
+        };
+
+        if config.max_spring_torque > 0.0 {
+            math::clamp_symmetric(config.max_spring_torque, torque)
+        } else {
+            torque
+        }
+    }
+
+    /// Drag on the spinning wheel, growing with the square of its speed as well
+    /// as linearly.
+    fn friction_torque(&self, config: &Config) -> f32 {
+        config.friction * self.velocity
+            + config.friction_quadratic * self.velocity * self.velocity.abs()
+    }
+
+    /// Self-aligning torque which only resists turning away from centre.
+    fn caster_torque(&self, config: &Config) -> f32 {
+        if self.velocity * self.angle > 0.0 {
+            config.caster * self.angle
+        } else {
+            0.0
+        }
+    }
+
+    /// Extra friction for a short while after letting go, fading out, so that a
+    /// wheel released mid-turn slows down predictably.
+    fn release_damping_torque(&self, config: &Config) -> f32 {
+        if self.release_damping_left <= 0.0 {
+            return 0.0;
+        }
+
+        let fade = self.release_damping_left / RELEASE_DAMPING_TIME;
+        config.release_damping * fade * self.velocity
+    }
+
+    /// Hard cap on how quickly the magnitude of the feedback torque may grow,
+    /// guarding against runaway effects. Falling torque is never held back.
+    fn limit_feedback_rise(&mut self, config: &Config, previous_torque: f32, dt: f32) {
+        if config.feedback_rise_limit <= 0.0 {
+            self.rise_limited = false;
+            return;
+        }
+
+        let max_magnitude = previous_torque.abs() + config.feedback_rise_limit * dt;
+        let limited = self.feedback_torque.abs() > max_magnitude;
+        if limited {
+            self.feedback_torque = max_magnitude.copysign(self.feedback_torque);
+        }
+
+        if limited && !self.rise_limited {
+            warn!("Feedback torque is rising too quickly; limiting it.");
+        } else if !limited && self.rise_limited {
+            info!("Feedback torque rise limiter released.");
+        }
+        self.rise_limited = limited;
+    }
+
+    /// Extra centring for a slow, free wheel near straight ahead. It fades out
+    /// towards the edge of the band so the feel elsewhere is unchanged.
+    fn center_assist_torque(&self, config: &Config) -> f32 {
+        const MAX_VELOCITY: f32 = 2.0;
+
+        let band = config.center_assist_band.to_radians();
+        if config.center_assist_strength <= 0.0
+            || band <= 0.0
+            || self.angle.abs() >= band
+            || self.velocity.abs() > MAX_VELOCITY
+        {
+            return 0.0;
+        }
+
+        config.center_assist_strength * self.angle * (1.0 - self.angle.abs() / band)
+    }
+
+    /// Run one step of `dt` of the wheel's own motion, when the pen isn't turning it.
+    fn advance(
+        &mut self,
+        mut device: Option<&mut Box<dyn Device>>,
+        config: &Config,
+        idle: bool,
+        frozen: bool,
+        half_range: f32,
+        dt: f32,
+    ) {
+        self.horn_cooldown = (self.horn_cooldown - dt).max(0.0);
+        self.release_damping_left = (self.release_damping_left - dt).max(0.0);
+
+        if !self.dragging
+            && let Some(target) = self.manual_target
+        {
+            self.follow_manual_target(device.as_deref_mut(), config, target, half_range, dt);
+        } else if !self.dragging && !frozen && idle && config.center_when_idle {
+            // Nothing is steering, so settle at straight ahead regardless of the spring.
+            self.feedback_torque = 0.0;
+            self.follow_manual_target(device.as_deref_mut(), config, 0.0, half_range, dt);
+        } else if !self.dragging && !frozen {
+            let mut feedback_normalised = device
                 .as_ref()
-                .map(|d| d.get_feedback())
-                .flatten()
+                .and_then(|d| d.get_feedback())
                 .unwrap_or(0.0);
-            self.feedback_torque = feedback_normalised * config.max_torque;
+            if config.feedback_invert {
+                feedback_normalised = -feedback_normalised;
+            }
+            let target_torque = feedback_normalised * config.max_torque;
+            let previous_torque = self.feedback_torque;
+            self.feedback_torque = if config.feedback_slew > 0.0 {
+                let max_change = config.feedback_slew * dt;
+                self.feedback_torque
+                    + math::clamp_symmetric(max_change, target_torque - self.feedback_torque)
+            } else {
+                target_torque
+            };
+            self.limit_feedback_rise(config, previous_torque, dt);
 
-            let friction_torque = config.friction * self.velocity;
-            let spring_torque = config.spring * self.angle;
-            let net_force = self.feedback_torque - friction_torque - spring_torque;
+            let friction_torque = self.friction_torque(config);
+            let spring_torque = self.spring_torque(config, half_range);
+            let detent_torque = self.detent_torque(config, half_range);
+            let assist_torque = self.center_assist_torque(config);
+            let caster_torque = self.caster_torque(config);
+            let release_torque = self.release_damping_torque(config);
+            let net_force = self.feedback_torque
+                - friction_torque
+                - release_torque
+                - spring_torque
+                - detent_torque
+                - assist_torque
+                - caster_torque;
             let angular_acceleration = net_force / config.inertia;
 
             self.velocity += angular_acceleration * dt;
@@ -60,33 +278,115 @@ impl Wheel {
                 dev.set_wheel(normalised);
             }
         }
+    }
 
-        self.angle = math::clamp_symmetric(half_range, self.angle);
+    /// Update the wheel for one tick of `dt`, running `steps` steps of its own
+    /// motion. The pen only moves once per tick, so its motion is applied once
+    /// however many steps are run.
+    pub fn update(
+        &mut self,
+        mut device: Option<&mut Box<dyn Device>>,
+        config: &Config,
+        pen: Option<Pen>,
+        dt: f32,
+        steps: u32,
+        frozen: bool,
+    ) {
+        let half_range = config.half_range_rad();
+
+        let idle = pen.is_none();
+        let pen = pen.unwrap_or_default();
+
+        if self.velocity.is_nan() || self.velocity.is_infinite() {
+            self.velocity = 0.0;
+        }
+
+        if self.angle.is_nan() || self.angle.is_infinite() {
+            self.angle = 0.0;
+        }
+
+        for _ in 0..steps {
+            self.advance(device.as_deref_mut(), config, idle, frozen, half_range, dt);
+        }
+
+        self.stop_at_lock(config, half_range);
+
+        if pen.tool == Tool::Eraser && config.eraser_action == EraserAction::Horn {
+            let pressed = config.horn_enabled && pen.pressure > config.pressure_threshold;
+            if pressed != self.honking
+                && let Some(dev) = device
+            {
+                dev.set_horn(pressed);
+            }
+
+            self.honking = pressed;
+            self.dragging = false;
+
+            return;
+        }
+
+        // Without the engage button held, the pen moves without turning the wheel.
+        let engaged = config
+            .engage_button
+            .is_none_or(|bit| pen.buttons & (1 << bit) != 0);
 
         // check if pen up
-        if pen.pressure <= config.pressure_threshold {
+        if pen.pressure <= config.pressure_threshold || !engaged {
             // stop honking
-            if self.honking {
-                if let Some(dev) = device {
-                    dev.set_horn(false);
-                }
+            if self.honking
+                && let Some(dev) = device
+            {
+                dev.set_horn(false);
+            }
+
+            // A tight turn can end with the pen near the centre, so don't let a
+            // quick re-grab sound the horn.
+            if self.dragging {
+                self.horn_cooldown = config.horn_cooldown;
+                self.release_damping_left = RELEASE_DAMPING_TIME;
             }
 
             self.honking = false;
             self.dragging = false;
+            self.horn_dwell = 0.0;
 
             return;
         }
 
         // wheel is held
 
-        if self.honking {
+        // The centre is used for steering in planar mode, so it can't be the horn.
+        if config.steering_model == SteeringModel::Planar {
+            self.steer_planar(device, config, &pen, half_range, dt);
             return;
         }
 
         let centre_dist = math::dist_sq(pen.x, pen.y).sqrt();
 
-        if !self.dragging && centre_dist <= config.horn_radius {
+        // Only let go of the horn once the pen is clearly outside of it, so that
+        // hovering at the edge doesn't toggle it.
+        if self.honking {
+            if centre_dist <= config.horn_radius + config.horn_hysteresis {
+                return;
+            }
+
+            self.honking = false;
+            if let Some(dev) = device.as_mut() {
+                dev.set_horn(false);
+            }
+        }
+
+        if config.horn_enabled
+            && !self.dragging
+            && self.horn_cooldown <= 0.0
+            && centre_dist <= config.horn_radius
+        {
+            // A quick pass through the centre shouldn't honk, only a deliberate press.
+            self.horn_dwell += dt;
+            if self.horn_dwell * 1000.0 < config.horn_dwell_ms as f32 {
+                return;
+            }
+
             // start honking
             self.honking = true;
             if let Some(dev) = device {
@@ -96,13 +396,16 @@ impl Wheel {
             return;
         }
 
+        self.horn_dwell = 0.0;
+
         // check if we were already dragging
         if self.dragging {
             let prev_theta = self.prev_pos.x.atan2(self.prev_pos.y);
             let theta = pen.x.atan2(pen.y);
 
             let delta_t = math::angle_delta(prev_theta, theta);
-            let adjusted = math::adjust_angle_delta(delta_t, centre_dist, config.base_radius);
+            let adjusted = math::adjust_angle_delta(delta_t, centre_dist, config.base_radius)
+                * config.sensitivity;
 
             let new_angle = self.angle + adjusted;
             self.prev_angle = self.angle;
@@ -121,3 +424,290 @@ impl Wheel {
         self.prev_pos.y = pen.y;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HALF_RANGE: f32 = 4.0;
+    const DT: f32 = 0.01;
+    const PRESSED: u32 = 1000;
+
+    /// Run one update with the pen at (x, y).
+    fn step(wheel: &mut Wheel, config: &Config, x: f32, y: f32, pressure: u32) {
+        let pen = Pen {
+            x,
+            y,
+            pressure,
+            ..Default::default()
+        };
+        wheel.update(None, config, Some(pen), DT, 1, false);
+    }
+
+    #[test]
+    fn quick_grab_after_a_tight_turn_does_not_honk() {
+        let config = Config::default();
+        let mut wheel = Wheel::default();
+
+        // A tight turn which ends with the pen lifting near the centre.
+        step(&mut wheel, &config, 0.4, 0.0, PRESSED);
+        step(&mut wheel, &config, 0.2, 0.2, PRESSED);
+        step(&mut wheel, &config, 0.0, 0.05, 0);
+        assert!(wheel.horn_cooldown > 0.0);
+
+        step(&mut wheel, &config, 0.0, 0.05, PRESSED);
+        assert!(!wheel.honking);
+
+        // Once the cooldown has passed, pressing the centre honks again.
+        step(&mut wheel, &config, 0.0, 0.05, 0);
+        for _ in 0..((config.horn_cooldown / DT) as usize + 1) {
+            step(&mut wheel, &config, 0.0, 0.05, 0);
+        }
+        step(&mut wheel, &config, 0.0, 0.05, PRESSED);
+        assert!(wheel.honking);
+    }
+
+    fn spring_torque_at(angle: f32, spring_exp: f32) -> f32 {
+        let config = Config {
+            spring: 2.0,
+            spring_exp,
+            max_spring_torque: 0.0,
+            ..Default::default()
+        };
+        let wheel = Wheel {
+            angle,
+            ..Default::default()
+        };
+        wheel.spring_torque(&config, HALF_RANGE)
+    }
+
+    #[test]
+    fn quick_pass_through_the_centre_does_not_honk() {
+        let config = Config {
+            horn_dwell_ms: 100,
+            ..Default::default()
+        };
+        let mut wheel = Wheel::default();
+
+        // Landing in the centre and leaving again within the dwell time.
+        for _ in 0..5 {
+            step(&mut wheel, &config, 0.0, 0.1, PRESSED);
+            assert!(!wheel.honking);
+        }
+        step(&mut wheel, &config, 0.0, 0.8, PRESSED);
+        step(&mut wheel, &config, 0.0, 0.8, 0);
+        assert!(!wheel.honking);
+        assert_eq!(wheel.horn_dwell, 0.0);
+
+        // Resting there for longer is a deliberate press.
+        for _ in 0..((config.horn_cooldown / DT) as usize + 1) {
+            step(&mut wheel, &config, 0.0, 0.1, 0);
+        }
+        for _ in 0..11 {
+            step(&mut wheel, &config, 0.0, 0.1, PRESSED);
+        }
+        assert!(wheel.honking);
+    }
+
+    #[test]
+    fn horn_is_steady_at_the_edge_of_its_radius() {
+        let config = Config::default();
+        let mut wheel = Wheel::default();
+
+        step(&mut wheel, &config, 0.0, 0.0, PRESSED);
+        assert!(wheel.honking);
+
+        // Wobbling either side of the radius, but within the hysteresis.
+        for i in 0..20 {
+            let wobble = if i % 2 == 0 { -0.02 } else { 0.05 };
+            let y = config.horn_radius + wobble;
+            step(&mut wheel, &config, 0.0, y, PRESSED);
+            assert!(wheel.honking, "released on wobble {i}");
+        }
+
+        let outside = config.horn_radius + config.horn_hysteresis + 0.05;
+        step(&mut wheel, &config, 0.0, outside, PRESSED);
+        assert!(!wheel.honking);
+    }
+
+    #[test]
+    fn first_contact_does_not_turn_the_wheel() {
+        let config = Config::default();
+        let mut wheel = Wheel {
+            angle: 0.5,
+            ..Default::default()
+        };
+
+        step(&mut wheel, &config, 0.8, 0.3, PRESSED);
+        assert!(wheel.dragging);
+        assert_eq!(wheel.angle, 0.5);
+    }
+
+    #[test]
+    fn relative_engage_keeps_the_wheel_where_it_is() {
+        let planar = |relative_engage| Config {
+            steering_model: SteeringModel::Planar,
+            relative_engage,
+            ..Default::default()
+        };
+        let turned = || Wheel {
+            angle: 1.0,
+            ..Default::default()
+        };
+
+        let mut wheel = turned();
+        step(&mut wheel, &planar(true), 0.5, 0.0, PRESSED);
+        assert!((wheel.angle - 1.0).abs() < 1e-6, "{}", wheel.angle);
+
+        // Otherwise, the wheel heads for where the pen is.
+        let mut wheel = turned();
+        step(&mut wheel, &planar(false), 0.5, 0.0, PRESSED);
+        assert!(wheel.angle < 1.0);
+    }
+
+    fn wheel_past_lock(velocity: f32) -> Wheel {
+        Wheel {
+            angle: HALF_RANGE + 0.1,
+            velocity,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn hard_stop_zeroes_velocity_at_lock() {
+        let config = Config {
+            lock_behavior: LockBehavior::HardStop,
+            ..Default::default()
+        };
+
+        let mut wheel = wheel_past_lock(5.0);
+        wheel.stop_at_lock(&config, HALF_RANGE);
+        assert_eq!(wheel.angle, HALF_RANGE);
+        assert_eq!(wheel.velocity, 0.0);
+
+        // Already heading back out of the lock, so it is left moving.
+        let mut wheel = wheel_past_lock(-5.0);
+        wheel.stop_at_lock(&config, HALF_RANGE);
+        assert_eq!(wheel.velocity, -5.0);
+    }
+
+    #[test]
+    fn bounce_reverses_some_velocity_at_lock() {
+        let config = Config {
+            lock_behavior: LockBehavior::Bounce,
+            ..Default::default()
+        };
+
+        let mut wheel = wheel_past_lock(5.0);
+        wheel.stop_at_lock(&config, HALF_RANGE);
+        assert_eq!(wheel.angle, HALF_RANGE);
+        assert!(wheel.velocity < 0.0 && wheel.velocity > -5.0);
+    }
+
+    #[test]
+    fn caster_only_resists_turning_away_from_centre() {
+        let config = Config {
+            caster: 3.0,
+            ..Default::default()
+        };
+        let moving = |angle, velocity| Wheel {
+            angle,
+            velocity,
+            ..Default::default()
+        };
+
+        // Subtracted from the net torque, so it pulls back towards the centre.
+        assert_eq!(moving(0.5, 1.0).caster_torque(&config), 1.5);
+        assert_eq!(moving(-0.5, -1.0).caster_torque(&config), -1.5);
+
+        // Heading back to the centre is left alone.
+        assert_eq!(moving(0.5, -1.0).caster_torque(&config), 0.0);
+        assert_eq!(moving(-0.5, 1.0).caster_torque(&config), 0.0);
+
+        let symmetric = Config {
+            caster: 0.0,
+            ..Default::default()
+        };
+        assert_eq!(moving(0.5, 1.0).caster_torque(&symmetric), 0.0);
+    }
+
+    #[test]
+    fn spring_torque_is_capped_on_release() {
+        let config = Config {
+            spring: 50.0,
+            max_spring_torque: 2.0,
+            friction: 0.0,
+            ..Default::default()
+        };
+        let mut wheel = Wheel {
+            angle: -3.0,
+            ..Default::default()
+        };
+
+        assert_eq!(wheel.spring_torque(&config, HALF_RANGE), -2.0);
+
+        // Let go far off centre: the snap back is no harder than the cap allows.
+        wheel.update(None, &config, None, DT, 1, false);
+        assert!(wheel.velocity > 0.0);
+        assert!(wheel.velocity <= 2.0 * DT / config.inertia + 1e-6);
+    }
+
+    #[test]
+    fn quadratic_friction_opposes_motion() {
+        let config = Config {
+            friction: 0.0,
+            friction_quadratic: 2.0,
+            ..Default::default()
+        };
+
+        for velocity in [-3.0, -0.5, 0.5, 3.0] {
+            let wheel = Wheel {
+                velocity,
+                ..Default::default()
+            };
+            let torque = wheel.friction_torque(&config);
+            // Subtracted from the net torque, so it must share the velocity's sign.
+            assert_eq!(torque.signum(), velocity.signum(), "at {velocity}");
+            assert_eq!(torque.abs(), 2.0 * velocity * velocity);
+        }
+    }
+
+    #[test]
+    fn exponent_of_one_is_the_linear_spring() {
+        for angle in [-4.0, -1.5, 0.0, 0.5, 3.0] {
+            assert_eq!(spring_torque_at(angle, 1.0), 2.0 * angle);
+        }
+    }
+
+    #[test]
+    fn higher_exponent_grows_faster_than_linear() {
+        let near = spring_torque_at(1.0, 2.0);
+        let far = spring_torque_at(2.0, 2.0);
+        assert!((far / near - 4.0).abs() < 1e-5, "{near} then {far}");
+
+        // Softer than linear near the centre, meeting it at lock.
+        assert!(near < spring_torque_at(1.0, 1.0));
+        assert!((spring_torque_at(HALF_RANGE, 2.0) - 2.0 * HALF_RANGE).abs() < 1e-5);
+    }
+
+    #[test]
+    fn curved_spring_keeps_its_direction() {
+        assert_eq!(spring_torque_at(-1.0, 2.0), -spring_torque_at(1.0, 2.0));
+        assert!(spring_torque_at(-1.0, 2.0) < 0.0);
+    }
+
+    #[test]
+    fn curved_spring_is_still_capped() {
+        let config = Config {
+            spring: 2.0,
+            spring_exp: 3.0,
+            max_spring_torque: 1.0,
+            ..Default::default()
+        };
+        let wheel = Wheel {
+            angle: HALF_RANGE,
+            ..Default::default()
+        };
+        assert_eq!(wheel.spring_torque(&config, HALF_RANGE), 1.0);
+    }
+}